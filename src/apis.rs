@@ -1,13 +1,23 @@
-use anyhow::Result;
+use std::{
+    ffi::OsStr,
+    path::{Component, Path},
+};
+
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use log::{debug, info, warn};
+use url::Url;
 
+pub mod nzbget;
 pub mod qbittorrent;
 pub mod radarr;
+pub mod sabnzbd;
 pub mod sonarr;
 pub mod types;
 
 #[async_trait]
 pub trait QBittorrentAPIInterface: Send + Sync {
+    async fn get_version(&self) -> Result<String>;
     async fn get_torrent_list(&self) -> Result<Vec<qbittorrent::Torrent>>;
     async fn delete_torrents(
         &self,
@@ -16,6 +26,17 @@ pub trait QBittorrentAPIInterface: Send + Sync {
     ) -> Result<()>;
 }
 
+/// SABnzbd or NZBGet.
+#[async_trait]
+pub trait UsenetAPIInterface: Send + Sync {
+    async fn get_version(&self) -> Result<String>;
+    async fn get_queue(&self) -> Result<Vec<types::UsenetJob>>;
+    async fn get_history(&self) -> Result<Vec<types::UsenetJob>>;
+    /// Removes finished `jobs` from the history, deleting their folders when
+    /// `delete_files` is set. Folders outside the download root are kept.
+    async fn delete_history(&self, jobs: Vec<&types::UsenetJob>, delete_files: bool) -> Result<()>;
+}
+
 #[async_trait]
 pub trait SonarrAndRadarrAPIInterface: Send + Sync {
     async fn get_system_status(&self) -> Result<types::SystemStatus>;
@@ -28,4 +49,194 @@ pub trait SonarrAndRadarrAPIInterface: Send + Sync {
         skip_redownload: Option<bool>,
         change_category: Option<bool>,
     ) -> Result<()>;
+    /// Searches for new releases of the episodes (Sonarr) or movies (Radarr)
+    /// with the given ids.
+    async fn search(&self, ids: Vec<i32>) -> Result<()>;
+    /// Stops monitoring `items`, so the *arr no longer grabs releases for them.
+    async fn unmonitor(&self, items: Vec<types::MediaItem>) -> Result<()>;
+    /// Lists the files of the download `download_id` through the manual import.
+    async fn get_import_candidates(&self, download_id: &str)
+    -> Result<Vec<types::ImportCandidate>>;
+    /// Imports the files at `paths` of the download `download_id`, as the
+    /// manual import identified them. Returns the id of the command that runs
+    /// the import.
+    async fn manual_import(&self, download_id: &str, paths: Vec<String>) -> Result<Option<i32>>;
+    /// Whether the command `id` is still queued or started.
+    async fn command_running(&self, id: i32) -> Result<bool>;
+}
+
+/// Joins `path` onto `host`, keeping the sub-path of a reverse-proxied host
+/// such as `http://host/sabnzbd`.
+pub fn endpoint(host: &Url, path: &str) -> Result<Url> {
+    let mut base = host.clone();
+    if !base.path().ends_with('/') {
+        base.set_path(&format!("{}/", base.path()));
+    }
+    Ok(base.join(path)?)
+}
+
+/// Returns the folder of `job` when it is named after the job and sits
+/// right below `download_root`, or below the folder of its category there.
+/// Anything else may be a parent of other downloads, such as a root
+/// configured one level too high.
+fn job_folder<'a>(download_root: &Path, job: &'a types::UsenetJob) -> Option<&'a Path> {
+    let path = Path::new(job.path.as_deref()?);
+    if !path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir | Component::CurDir))
+    {
+        return None;
+    }
+    let relative: Vec<&OsStr> = path.strip_prefix(download_root).ok()?.iter().collect();
+    let category = OsStr::new(&job.category);
+    let expected_depth = match relative.as_slice() {
+        [name] => *name != category,
+        [parent, _] => !job.category.is_empty() && *parent == category,
+        _ => false,
+    };
+    (expected_depth && relative.last() == Some(&OsStr::new(&job.name))).then_some(path)
+}
+
+/// Deletes the folders of `jobs`, which the usenet client can't delete on its
+/// own. Folders that `job_folder` does not accept below `download_root` are
+/// skipped with a warning, as are all of them when no root is configured.
+pub async fn delete_job_folders(
+    download_root: Option<&Path>,
+    jobs: &[&types::UsenetJob],
+) -> Result<()> {
+    for job in jobs.iter().filter(|job| job.path.is_some()) {
+        let Some(path) = download_root.and_then(|root| job_folder(root, job)) else {
+            warn!(
+                "Not deleting the folder of '{}', as it is not its own folder below the download root",
+                job.name
+            );
+            continue;
+        };
+        if !path.exists() {
+            debug!("The folder of '{}' is already gone", job.name);
+            continue;
+        }
+        tokio::fs::remove_dir_all(path)
+            .await
+            .with_context(|| format!("Could not delete '{}'", path.display()))?;
+        info!("Deleted the folder '{}' of '{}'", path.display(), job.name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::types::UsenetJob;
+
+    fn job(category: &str, path: &str) -> UsenetJob {
+        UsenetJob {
+            id: "1".to_string(),
+            download_id: "1".to_string(),
+            name: "Some.Show.S01E01".to_string(),
+            category: category.to_string(),
+            finished: true,
+            failure: None,
+            fail_message: None,
+            path: Some(path.to_string()),
+            completed_at: None,
+        }
+    }
+
+    // ── endpoint ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn endpoint_keeps_sub_path() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert_eq!(
+            endpoint(&url("http://host/sabnzbd"), "api").unwrap(),
+            url("http://host/sabnzbd/api")
+        );
+        assert_eq!(
+            endpoint(&url("http://host/nzbget/"), "jsonrpc").unwrap(),
+            url("http://host/nzbget/jsonrpc")
+        );
+        assert_eq!(
+            endpoint(&url("http://host:6789"), "jsonrpc").unwrap(),
+            url("http://host:6789/jsonrpc")
+        );
+    }
+
+    // ── job folders ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn job_folder_must_be_below_the_root() {
+        let root = Path::new("/downloads/complete");
+        let below = job("tv", "/downloads/complete/tv/Some.Show.S01E01");
+        assert_eq!(
+            job_folder(root, &below),
+            Some(Path::new("/downloads/complete/tv/Some.Show.S01E01"))
+        );
+        let direct = job("", "/downloads/complete/Some.Show.S01E01");
+        assert_eq!(
+            job_folder(root, &direct),
+            Some(Path::new("/downloads/complete/Some.Show.S01E01"))
+        );
+        assert_eq!(job_folder(root, &job("tv", "/downloads/complete")), None);
+        assert_eq!(job_folder(root, &job("tv", "/downloads/complete/")), None);
+        assert_eq!(job_folder(root, &job("tv", "/downloads/complete/tv")), None);
+        assert_eq!(job_folder(root, &job("tv", "/downloads/other/Show")), None);
+        assert_eq!(
+            job_folder(root, &job("tv", "/downloads/complete/tv/../../etc")),
+            None
+        );
+        assert_eq!(job_folder(root, &job("tv", "complete/tv/Show")), None);
+        assert_eq!(job_folder(root, &job("", "/downloads/complete")), None);
+    }
+
+    #[test]
+    fn job_folder_must_be_named_after_the_job() {
+        let root = Path::new("/downloads/complete");
+        assert_eq!(
+            job_folder(root, &job("tv", "/downloads/complete/tv/Other.Show.S01E01")),
+            None
+        );
+        assert_eq!(
+            job_folder(root, &job("", "/downloads/complete/Season 1")),
+            None
+        );
+    }
+
+    #[test]
+    fn job_folder_must_be_at_the_expected_depth() {
+        // A root set one level too high must not reach the shared folders
+        let root = Path::new("/data");
+        let job = job("tv", "/data/usenet/complete/Some.Show.S01E01");
+        assert_eq!(job_folder(root, &job), None);
+        let job = UsenetJob {
+            name: "complete".to_string(),
+            ..job
+        };
+        assert_eq!(job_folder(root, &job), None);
+    }
+
+    #[tokio::test]
+    async fn delete_job_folders_only_below_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("complete");
+        let inside = root.join("tv").join("Some.Show.S01E01");
+        let outside = dir.path().join("Other.Show.S01E01");
+        std::fs::create_dir_all(&inside).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        let jobs = [
+            job("tv", inside.to_str().unwrap()),
+            job("tv", outside.to_str().unwrap()),
+            job("tv", root.join("tv").to_str().unwrap()),
+        ];
+        let jobs: Vec<&UsenetJob> = jobs.iter().collect();
+
+        delete_job_folders(None, &jobs).await.unwrap();
+        assert!(inside.exists());
+
+        delete_job_folders(Some(&root), &jobs).await.unwrap();
+        assert!(!inside.exists());
+        assert!(outside.exists());
+        assert!(root.join("tv").exists());
+    }
 }
@@ -0,0 +1,327 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::json;
+use time::OffsetDateTime;
+use url::Url;
+
+use crate::{
+    apis::{
+        UsenetAPIInterface, delete_job_folders, endpoint,
+        types::{UsenetFailure, UsenetJob},
+    },
+    config::NzbgetConfig,
+};
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Parameter {
+    name: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Group {
+    #[serde(rename = "NZBID")]
+    nzb_id: i64,
+    #[serde(rename = "NZBName")]
+    nzb_name: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    parameters: Vec<Parameter>,
+    /// Share of the articles still available, in per mille
+    health: i64,
+    critical_health: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct HistoryItem {
+    #[serde(rename = "NZBID")]
+    nzb_id: i64,
+    name: String,
+    #[serde(default)]
+    category: String,
+    status: String,
+    #[serde(default)]
+    unpack_status: String,
+    #[serde(default)]
+    parameters: Vec<Parameter>,
+    #[serde(default)]
+    dest_dir: String,
+    /// Where a post-processing script moved the files, empty otherwise
+    #[serde(default)]
+    final_dir: String,
+    history_time: Option<i64>,
+}
+
+/// The *arrs tag the downloads they send with a `drone` parameter and know
+/// them by its value, falling back to the NZBID for other downloads.
+fn download_id(nzb_id: i64, parameters: &[Parameter]) -> String {
+    parameters
+        .iter()
+        .find(|parameter| parameter.name == "drone")
+        .map_or_else(|| nzb_id.to_string(), |parameter| parameter.value.clone())
+}
+
+/// Classifies the `Status` of a history item, written as `KIND/DETAIL` such
+/// as `FAILURE/PAR` or `SUCCESS/ALL`.
+fn history_failure(status: &str, unpack_status: &str) -> Option<UsenetFailure> {
+    if unpack_status == "PASSWORD" {
+        return Some(UsenetFailure::Encrypted);
+    }
+    match status.split_once('/')? {
+        (_, "PASSWORD") => Some(UsenetFailure::Encrypted),
+        ("FAILURE" | "WARNING" | "DELETED", "HEALTH") => Some(UsenetFailure::MissingArticles),
+        ("FAILURE", "PAR") | ("WARNING", "DAMAGED") => Some(UsenetFailure::RepairFailed),
+        ("FAILURE", "UNPACK") => Some(UsenetFailure::UnpackFailed),
+        ("FAILURE", _) => Some(UsenetFailure::Other),
+        _ => None,
+    }
+}
+
+impl From<Group> for UsenetJob {
+    fn from(group: Group) -> Self {
+        // NZBGet deletes or pauses downloads below their critical health
+        let failure =
+            (group.health < group.critical_health).then_some(UsenetFailure::MissingArticles);
+        UsenetJob {
+            id: group.nzb_id.to_string(),
+            download_id: download_id(group.nzb_id, &group.parameters),
+            name: group.nzb_name,
+            category: group.category,
+            finished: false,
+            failure,
+            fail_message: failure.map(|_| {
+                format!(
+                    "Health {:.1}% below critical {:.1}%",
+                    group.health as f64 / 10.0,
+                    group.critical_health as f64 / 10.0
+                )
+            }),
+            path: None,
+            completed_at: None,
+        }
+    }
+}
+
+impl From<HistoryItem> for UsenetJob {
+    fn from(item: HistoryItem) -> Self {
+        let failure = history_failure(&item.status, &item.unpack_status);
+        UsenetJob {
+            id: item.nzb_id.to_string(),
+            download_id: download_id(item.nzb_id, &item.parameters),
+            name: item.name,
+            category: item.category,
+            finished: true,
+            failure,
+            fail_message: failure.map(|_| item.status),
+            path: [item.final_dir, item.dest_dir]
+                .into_iter()
+                .find(|dir| !dir.is_empty()),
+            completed_at: item
+                .history_time
+                .and_then(|time| OffsetDateTime::from_unix_timestamp(time).ok()),
+        }
+    }
+}
+
+pub struct NzbgetAPI {
+    client: reqwest::Client,
+    host: Url,
+    username: String,
+    password: String,
+    download_root: Option<PathBuf>,
+}
+
+impl NzbgetAPI {
+    pub fn new(config: &NzbgetConfig) -> Self {
+        NzbgetAPI {
+            client: reqwest::Client::new(),
+            host: config.host.clone(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            download_root: config.download_root.clone(),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T> {
+        let url = endpoint(&self.host, "jsonrpc")?;
+        let response: RpcResponse<T> = self
+            .client
+            .post(url)
+            .basic_auth(&self.username, Some(&self.password))
+            .json(&json!({ "method": method, "params": params }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Could not call NZBGet '{method}'"))?
+            .json()
+            .await
+            .with_context(|| format!("Invalid response to NZBGet '{method}'"))?;
+        if let Some(error) = response.error {
+            bail!("NZBGet '{method}' failed: {}", error.message);
+        }
+        response
+            .result
+            .with_context(|| format!("NZBGet '{method}' returned no result"))
+    }
+}
+
+#[async_trait]
+impl UsenetAPIInterface for NzbgetAPI {
+    async fn get_version(&self) -> Result<String> {
+        self.call("version", json!([])).await
+    }
+
+    async fn get_queue(&self) -> Result<Vec<UsenetJob>> {
+        let groups: Vec<Group> = self.call("listgroups", json!([0])).await?;
+        Ok(groups.into_iter().map(UsenetJob::from).collect())
+    }
+
+    async fn get_history(&self) -> Result<Vec<UsenetJob>> {
+        let items: Vec<HistoryItem> = self.call("history", json!([false])).await?;
+        Ok(items.into_iter().map(UsenetJob::from).collect())
+    }
+
+    async fn delete_history(&self, jobs: Vec<&UsenetJob>, delete_files: bool) -> Result<()> {
+        if jobs.is_empty() {
+            return Ok(());
+        }
+        let ids = jobs
+            .iter()
+            .map(|job| job.id.parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .context("Invalid NZBGet job id")?;
+        // NZBGet can't delete the files of history items, so they are
+        // deleted here first, which needs the same paths as NZBGet. The
+        // history items are kept when that fails, to retry on the next run
+        if delete_files {
+            delete_job_folders(self.download_root.as_deref(), &jobs).await?;
+        }
+        let deleted: bool = self
+            .call("editqueue", json!(["HistoryFinalDelete", "", ids]))
+            .await?;
+        if !deleted {
+            bail!("NZBGet did not delete history items {ids:?}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── failures ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn history_failure_from_status() {
+        assert_eq!(history_failure("SUCCESS/ALL", "SUCCESS"), None);
+        assert_eq!(history_failure("DELETED/MANUAL", "NONE"), None);
+        assert_eq!(
+            history_failure("FAILURE/HEALTH", "NONE"),
+            Some(UsenetFailure::MissingArticles)
+        );
+        assert_eq!(
+            history_failure("FAILURE/PAR", "NONE"),
+            Some(UsenetFailure::RepairFailed)
+        );
+        assert_eq!(
+            history_failure("FAILURE/UNPACK", "PASSWORD"),
+            Some(UsenetFailure::Encrypted)
+        );
+        assert_eq!(
+            history_failure("FAILURE/UNPACK", "FAILURE"),
+            Some(UsenetFailure::UnpackFailed)
+        );
+        assert_eq!(
+            history_failure("FAILURE/MOVE", "SUCCESS"),
+            Some(UsenetFailure::Other)
+        );
+    }
+
+    // ── responses ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn groups_become_jobs() {
+        let groups: Vec<Group> = serde_json::from_str(
+            r#"[{
+                "NZBID": 12,
+                "NZBName": "Some.Show.S01E01",
+                "Category": "tv",
+                "Parameters": [{"Name": "drone", "Value": "abc123"}],
+                "Health": 850,
+                "CriticalHealth": 900
+            }]"#,
+        )
+        .unwrap();
+        let job = UsenetJob::from(groups.into_iter().next().unwrap());
+        assert_eq!(job.id, "12");
+        assert_eq!(job.download_id, "abc123");
+        assert!(!job.finished);
+        assert_eq!(job.failure, Some(UsenetFailure::MissingArticles));
+        assert_eq!(
+            job.fail_message.as_deref(),
+            Some("Health 85.0% below critical 90.0%")
+        );
+    }
+
+    #[test]
+    fn history_items_become_jobs() {
+        let items: Vec<HistoryItem> = serde_json::from_str(
+            r#"[{
+                "NZBID": 7,
+                "Name": "Other.Show.S01E01",
+                "Category": "tv",
+                "Status": "FAILURE/PAR",
+                "UnpackStatus": "NONE",
+                "Parameters": [],
+                "DestDir": "/downloads/Other.Show.S01E01",
+                "HistoryTime": 1700000000
+            }]"#,
+        )
+        .unwrap();
+        let job = UsenetJob::from(items.into_iter().next().unwrap());
+        assert_eq!(job.download_id, "7");
+        assert!(job.finished);
+        assert_eq!(job.failure, Some(UsenetFailure::RepairFailed));
+        assert_eq!(job.fail_message.as_deref(), Some("FAILURE/PAR"));
+        assert_eq!(job.path.as_deref(), Some("/downloads/Other.Show.S01E01"));
+    }
+
+    #[test]
+    fn history_items_prefer_final_dir() {
+        let items: Vec<HistoryItem> = serde_json::from_str(
+            r#"[{
+                "NZBID": 8,
+                "Name": "Other.Show.S01E02",
+                "Status": "SUCCESS/ALL",
+                "DestDir": "/downloads/intermediate/Other.Show.S01E02",
+                "FinalDir": "/downloads/tv/Other.Show.S01E02"
+            }]"#,
+        )
+        .unwrap();
+        let job = UsenetJob::from(items.into_iter().next().unwrap());
+        assert_eq!(job.path.as_deref(), Some("/downloads/tv/Other.Show.S01E02"));
+    }
+}
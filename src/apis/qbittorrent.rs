@@ -1,31 +1,37 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use log::error;
-use qbit_rs::{
-    Qbit,
-    model::{Credential, GetTorrentListArg},
-};
+use qbit_rs::{Qbit, model::Credential};
 use time::OffsetDateTime;
+use tokio::sync::{Mutex, Semaphore};
 
 use crate::{apis::QBittorrentAPIInterface, config::QBittorrentConfig};
 pub struct QBittorrentAPI {
     api: Qbit,
+    max_concurrent_requests: usize,
+    cache: Mutex<TorrentCache>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Torrent {
     pub name: String,
     pub hash: String,
-    #[allow(unused)]
     pub total_size: i64,
     pub save_path: String,
     pub category: String,
     pub ratio: f64,
     pub seeding_time: Duration,
     pub progress: f64,
-    #[allow(unused)]
+    /// Current download speed in bytes per second
+    pub dlspeed: i64,
+    pub tags: Vec<String>,
+    pub added_on: Option<OffsetDateTime>,
     pub last_activity: Option<OffsetDateTime>,
     pub trackers: Vec<qbit_rs::model::Tracker>,
     pub contents: Vec<qbit_rs::model::TorrentContent>,
@@ -45,22 +51,86 @@ impl std::hash::Hash for Torrent {
     }
 }
 
-async fn process_torrent(api: Qbit, torrent: qbit_rs::model::Torrent) -> Result<Torrent> {
+/// Fields of a maindata update after which the contents and trackers of a
+/// torrent have to be fetched again.
+const DETAIL_FIELDS: [&str; 7] = [
+    "state",
+    "progress",
+    "tracker",
+    "trackers_count",
+    "save_path",
+    "total_size",
+    "name",
+];
+
+/// Longest the contents and trackers of a torrent are cached, as maindata
+/// does not report every change to them, such as an edited tracker URL.
+const DETAILS_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// Torrent as last seen through `/sync/maindata`, together with the details
+/// that maindata does not report.
+struct CachedTorrent {
+    info: qbit_rs::model::Torrent,
+    trackers: Option<Vec<qbit_rs::model::Tracker>>,
+    contents: Option<Vec<qbit_rs::model::TorrentContent>>,
+    /// When the contents and trackers were last fetched
+    fetched_at: Option<Instant>,
+}
+
+impl CachedTorrent {
+    /// Whether the contents and trackers are missing or too old at `now`.
+    fn needs_details(&self, now: Instant) -> bool {
+        self.trackers.is_none()
+            || self.contents.is_none()
+            || self
+                .fetched_at
+                .is_none_or(|fetched_at| now.duration_since(fetched_at) >= DETAILS_MAX_AGE)
+    }
+}
+
+#[derive(Default)]
+struct TorrentCache {
+    rid: i64,
+    torrents: HashMap<String, CachedTorrent>,
+}
+
+/// Applies the fields present in a partial maindata `update` on top of `info`.
+fn merge_torrent_info(
+    info: &qbit_rs::model::Torrent,
+    update: &qbit_rs::model::Torrent,
+) -> Result<qbit_rs::model::Torrent> {
+    let mut merged = serde_json::to_value(info)?;
+    if let (Some(merged), serde_json::Value::Object(update)) =
+        (merged.as_object_mut(), serde_json::to_value(update)?)
+    {
+        for (key, value) in update {
+            if !value.is_null() {
+                merged.insert(key, value);
+            }
+        }
+    }
+    Ok(serde_json::from_value(merged)?)
+}
+
+/// Whether a partial maindata `update` changed anything that the contents or
+/// trackers of the torrent depend on.
+fn needs_details_refresh(update: &qbit_rs::model::Torrent) -> Result<bool> {
+    let update = serde_json::to_value(update)?;
+    Ok(DETAIL_FIELDS
+        .iter()
+        .any(|field| update.get(field).is_some_and(|value| !value.is_null())))
+}
+
+fn build_torrent(
+    torrent: qbit_rs::model::Torrent,
+    trackers: Vec<qbit_rs::model::Tracker>,
+    contents: Vec<qbit_rs::model::TorrentContent>,
+) -> Result<Torrent> {
     let name = torrent.name.context("Torrent missing name")?;
     let hash = torrent.hash.context("Torrent missing hash")?;
     let total_size = torrent.total_size.context("Torrent missing total_size")?;
     let save_path = torrent.save_path.context("Torrent missing save_path")?;
 
-    let contents = api
-        .get_torrent_contents(&hash, None)
-        .await
-        .map_err(|e| anyhow!("Could not retrieve contents for torrent '{}': {e}", name))?;
-
-    let trackers = api
-        .get_torrent_trackers(&hash)
-        .await
-        .map_err(|e| anyhow!("Could not retrieve trackers for torrent '{}': {e}", name))?;
-
     Ok(Torrent {
         name,
         hash,
@@ -72,6 +142,17 @@ async fn process_torrent(api: Qbit, torrent: qbit_rs::model::Torrent) -> Result<
             torrent.seeding_time.unwrap_or(0).try_into().unwrap_or(0),
         ),
         progress: torrent.progress.unwrap_or(0.0),
+        dlspeed: torrent.dlspeed.unwrap_or(0),
+        tags: torrent
+            .tags
+            .unwrap_or_default()
+            .split(',')
+            .map(|tag| tag.trim().to_owned())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        added_on: torrent
+            .added_on
+            .and_then(|ts| OffsetDateTime::from_unix_timestamp(ts).ok()),
         last_activity: torrent
             .last_activity
             .and_then(|ts| OffsetDateTime::from_unix_timestamp(ts).ok()),
@@ -80,23 +161,111 @@ async fn process_torrent(api: Qbit, torrent: qbit_rs::model::Torrent) -> Result<
     })
 }
 
-async fn get_torrents(api: &Qbit) -> Result<Vec<Torrent>> {
-    let torrent_list = api.get_torrent_list(GetTorrentListArg::default()).await?;
+async fn fetch_details(
+    api: Qbit,
+    hash: String,
+) -> Result<(
+    Vec<qbit_rs::model::Tracker>,
+    Vec<qbit_rs::model::TorrentContent>,
+)> {
+    let contents = api
+        .get_torrent_contents(&hash, None)
+        .await
+        .map_err(|e| anyhow!("Could not retrieve contents for torrent '{}': {e}", hash))?;
+
+    let trackers = api
+        .get_torrent_trackers(&hash)
+        .await
+        .map_err(|e| anyhow!("Could not retrieve trackers for torrent '{}': {e}", hash))?;
+
+    Ok((trackers, contents))
+}
+
+/// Brings `cache` up to date through `/sync/maindata` and fetches the contents
+/// and trackers of the torrents that are new or changed since the last sync.
+async fn sync_torrents(
+    api: &Qbit,
+    cache: &mut TorrentCache,
+    max_concurrent_requests: usize,
+) -> Result<Vec<Torrent>> {
+    let data = api
+        .sync(cache.rid)
+        .await
+        .map_err(|e| anyhow!("Could not sync torrents: {e}"))?;
+
+    if data.full_update.unwrap_or(false) {
+        cache.torrents.clear();
+    }
+    for hash in data.torrents_removed.unwrap_or_default() {
+        cache.torrents.remove(&hash);
+    }
+    for (hash, update) in data.torrents.unwrap_or_default() {
+        match cache.torrents.get_mut(&hash) {
+            Some(cached) => {
+                cached.info = merge_torrent_info(&cached.info, &update)?;
+                if needs_details_refresh(&update)? {
+                    cached.trackers = None;
+                    cached.contents = None;
+                }
+            }
+            None => {
+                let mut info = update;
+                info.hash = Some(hash.clone());
+                cache.torrents.insert(
+                    hash,
+                    CachedTorrent {
+                        info,
+                        trackers: None,
+                        contents: None,
+                        fetched_at: None,
+                    },
+                );
+            }
+        }
+    }
+    cache.rid = data.rid;
 
+    let semaphore = Arc::new(Semaphore::new(max_concurrent_requests.max(1)));
     let mut set = tokio::task::JoinSet::new();
-    for torrent in torrent_list {
+    let now = Instant::now();
+    for (hash, cached) in &cache.torrents {
+        if !cached.needs_details(now) {
+            continue;
+        }
         let api = api.clone();
-        set.spawn(async move { process_torrent(api, torrent).await });
+        let hash = hash.clone();
+        let semaphore = semaphore.clone();
+        set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let details = fetch_details(api, hash.clone()).await;
+            (hash, details)
+        });
     }
 
-    let mut results = Vec::new();
     while let Some(join_result) = set.join_next().await {
         match join_result {
-            Ok(Ok(torrent)) => results.push(torrent),
-            Ok(Err(e)) => error!("Failed to process torrent: {e}"),
+            Ok((hash, Ok((trackers, contents)))) => {
+                if let Some(cached) = cache.torrents.get_mut(&hash) {
+                    cached.trackers = Some(trackers);
+                    cached.contents = Some(contents);
+                    cached.fetched_at = Some(now);
+                }
+            }
+            Ok((_, Err(e))) => error!("Failed to process torrent: {e}"),
             Err(e) => error!("Torrent processing task panicked: {e}"),
         }
     }
+
+    let mut results = Vec::new();
+    for cached in cache.torrents.values() {
+        let (Some(trackers), Some(contents)) = (&cached.trackers, &cached.contents) else {
+            continue;
+        };
+        match build_torrent(cached.info.clone(), trackers.clone(), contents.clone()) {
+            Ok(torrent) => results.push(torrent),
+            Err(e) => error!("Failed to process torrent: {e}"),
+        }
+    }
     Ok(results)
 }
 
@@ -107,14 +276,21 @@ impl QBittorrentAPI {
                 config.host.clone(),
                 Credential::new(config.username.clone(), config.password.clone()),
             ),
+            max_concurrent_requests: config.max_concurrent_requests,
+            cache: Mutex::new(TorrentCache::default()),
         }
     }
 }
 
 #[async_trait]
 impl QBittorrentAPIInterface for QBittorrentAPI {
+    async fn get_version(&self) -> Result<String> {
+        self.api.get_version().await.map_err(|e| anyhow!("{e}"))
+    }
+
     async fn get_torrent_list(&self) -> Result<Vec<Torrent>> {
-        get_torrents(&self.api).await
+        let mut cache = self.cache.lock().await;
+        sync_torrents(&self.api, &mut cache, self.max_concurrent_requests).await
     }
 
     async fn delete_torrents(
@@ -129,3 +305,92 @@ impl QBittorrentAPIInterface for QBittorrentAPI {
             .map_err(|e| anyhow!("{e}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── merge_torrent_info ───────────────────────────────────────────────────
+
+    #[test]
+    fn merge_torrent_info_keeps_fields_missing_from_update() {
+        let info = qbit_rs::model::Torrent {
+            name: Some("t".to_string()),
+            ratio: Some(1.0),
+            category: Some("movies".to_string()),
+            ..Default::default()
+        };
+        let update = qbit_rs::model::Torrent {
+            ratio: Some(2.5),
+            ..Default::default()
+        };
+        let merged = merge_torrent_info(&info, &update).unwrap();
+        assert_eq!(merged.name.as_deref(), Some("t"));
+        assert_eq!(merged.category.as_deref(), Some("movies"));
+        assert_eq!(merged.ratio, Some(2.5));
+    }
+
+    // ── needs_details_refresh ────────────────────────────────────────────────
+
+    #[test]
+    fn needs_details_refresh_ignores_transfer_stats() {
+        let update = qbit_rs::model::Torrent {
+            ratio: Some(2.5),
+            uploaded: Some(1024),
+            upspeed: Some(10),
+            ..Default::default()
+        };
+        assert!(!needs_details_refresh(&update).unwrap());
+    }
+
+    #[test]
+    fn needs_details_refresh_on_progress_or_tracker_change() {
+        let progress = qbit_rs::model::Torrent {
+            progress: Some(0.5),
+            ..Default::default()
+        };
+        let tracker = qbit_rs::model::Torrent {
+            tracker: Some("https://tracker.example.org/announce".to_string()),
+            ..Default::default()
+        };
+        assert!(needs_details_refresh(&progress).unwrap());
+        assert!(needs_details_refresh(&tracker).unwrap());
+    }
+
+    #[test]
+    fn needs_details_refresh_on_trackers_count_change() {
+        let update = qbit_rs::model::Torrent {
+            trackers_count: Some(3),
+            ..Default::default()
+        };
+        assert!(needs_details_refresh(&update).unwrap());
+    }
+
+    #[test]
+    fn cached_details_expire() {
+        let now = Instant::now();
+        let mut cached = CachedTorrent {
+            info: qbit_rs::model::Torrent::default(),
+            trackers: Some(vec![]),
+            contents: Some(vec![]),
+            fetched_at: Some(now),
+        };
+        assert!(!cached.needs_details(now));
+        assert!(cached.needs_details(now + DETAILS_MAX_AGE));
+        cached.contents = None;
+        assert!(cached.needs_details(now));
+    }
+
+    // ── build_torrent ────────────────────────────────────────────────────────
+
+    #[test]
+    fn build_torrent_requires_hash() {
+        let info = qbit_rs::model::Torrent {
+            name: Some("t".to_string()),
+            total_size: Some(1),
+            save_path: Some("/data".to_string()),
+            ..Default::default()
+        };
+        assert!(build_torrent(info, vec![], vec![]).is_err());
+    }
+}
@@ -3,12 +3,17 @@ use async_trait::async_trait;
 use radarr::{
     apis::{
         Api, ApiClient,
+        command_api::{ApiV3CommandIdGetParams, ApiV3CommandPostParams},
         configuration::{ApiKey, Configuration},
+        manual_import_api::ApiV3ManualimportGetParams,
+        movie_editor_api::ApiV3MovieEditorPutParams,
         queue_api::{ApiV3QueueBulkDeleteParams, ApiV3QueueGetParams},
     },
     models::{
-        RadarrHealthCheckResult, RadarrQueueBulkResource, RadarrQueueResource, RadarrQueueStatus,
-        RadarrSystemResource, RadarrTrackedDownloadState, RadarrTrackedDownloadStatus,
+        RadarrCommandResource, RadarrCommandStatus, RadarrHealthCheckResult,
+        RadarrManualImportReprocessResource, RadarrManualImportResource, RadarrMovieEditorResource,
+        RadarrQueueBulkResource, RadarrQueueResource, RadarrQueueStatus, RadarrSystemResource,
+        RadarrTrackedDownloadState, RadarrTrackedDownloadStatus,
         RadarrTrackedDownloadStatusMessage,
     },
 };
@@ -18,8 +23,8 @@ use crate::{
     apis::{
         SonarrAndRadarrAPIInterface,
         types::{
-            QueueResource, QueueStatus, SystemStatus, TrackedDownloadState, TrackedDownloadStatus,
-            TrackedDownloadStatusMessage,
+            ImportCandidate, MediaItem, QueueResource, QueueStatus, SystemStatus,
+            TrackedDownloadState, TrackedDownloadStatus, TrackedDownloadStatusMessage,
         },
     },
     config::RadarrConfig,
@@ -103,6 +108,10 @@ impl TryFrom<RadarrQueueResource> for QueueResource {
             size: r.size.unwrap_or_default() as i64,
             title: r.title.flatten(),
             download_id: r.download_id.flatten(),
+            series_id: None,
+            episode_id: None,
+            episode_ids: Vec::new(),
+            movie_id: r.movie_id.flatten(),
             status: r
                 .status
                 .map(QueueStatus::from)
@@ -128,11 +137,45 @@ impl TryFrom<RadarrQueueResource> for QueueResource {
     }
 }
 
+impl From<&RadarrManualImportResource> for ImportCandidate {
+    fn from(r: &RadarrManualImportResource) -> Self {
+        ImportCandidate {
+            path: r.path.clone().flatten().unwrap_or_default(),
+            series_id: None,
+            episode_ids: Vec::new(),
+            movie_id: r.movie.as_ref().and_then(|movie| movie.id),
+            rejections: r
+                .rejections
+                .iter()
+                .flatten()
+                .flatten()
+                .filter_map(|rejection| rejection.reason.clone().flatten())
+                .collect(),
+        }
+    }
+}
+
 pub struct RadarrAPI {
     api: ApiClient,
 }
 
 impl RadarrAPI {
+    async fn get_manual_import(
+        &self,
+        download_id: &str,
+    ) -> Result<Vec<RadarrManualImportResource>> {
+        let params = ApiV3ManualimportGetParams {
+            download_id: Some(download_id.to_owned()),
+            filter_existing_files: Some(true),
+            ..Default::default()
+        };
+        self.api
+            .manual_import_api()
+            .api_v3_manualimport_get(params)
+            .await
+            .map_err(|e| anyhow!("Could not list files of download '{download_id}': {e}"))
+    }
+
     pub fn new(app_config: &RadarrConfig) -> Self {
         let mut config = Configuration::default();
         config.base_path = app_config
@@ -217,4 +260,104 @@ impl SonarrAndRadarrAPIInterface for RadarrAPI {
             .api_v3_queue_bulk_delete(params)
             .await?)
     }
+
+    async fn search(&self, ids: Vec<i32>) -> Result<()> {
+        let params = ApiV3CommandPostParams {
+            radarr_command_resource: Some(RadarrCommandResource {
+                name: Some(Some("MoviesSearch".to_owned())),
+                movie_ids: Some(Some(ids)),
+                ..Default::default()
+            }),
+        };
+        self.api
+            .command_api()
+            .api_v3_command_post(params)
+            .await
+            .map_err(|e| anyhow!("Could not start MoviesSearch command: {e}"))?;
+        Ok(())
+    }
+
+    async fn unmonitor(&self, items: Vec<MediaItem>) -> Result<()> {
+        let movie_ids = items
+            .into_iter()
+            .map(|item| match item {
+                MediaItem::Movie(id) => Ok(id),
+                other => Err(anyhow!("Radarr has no {other:?}")),
+            })
+            .collect::<Result<Vec<i32>>>()?;
+        let params = ApiV3MovieEditorPutParams {
+            radarr_movie_editor_resource: Some(RadarrMovieEditorResource {
+                movie_ids: Some(Some(movie_ids)),
+                monitored: Some(Some(false)),
+                ..Default::default()
+            }),
+        };
+        self.api
+            .movie_editor_api()
+            .api_v3_movie_editor_put(params)
+            .await
+            .map_err(|e| anyhow!("Could not unmonitor movies: {e}"))?;
+        Ok(())
+    }
+
+    async fn get_import_candidates(&self, download_id: &str) -> Result<Vec<ImportCandidate>> {
+        Ok(self
+            .get_manual_import(download_id)
+            .await?
+            .iter()
+            .map(ImportCandidate::from)
+            .collect())
+    }
+
+    async fn manual_import(&self, download_id: &str, paths: Vec<String>) -> Result<Option<i32>> {
+        let files = self
+            .get_manual_import(download_id)
+            .await?
+            .into_iter()
+            .filter(|r| {
+                r.path
+                    .clone()
+                    .flatten()
+                    .is_some_and(|path| paths.contains(&path))
+            })
+            .map(|r| RadarrManualImportReprocessResource {
+                movie_id: r.movie.as_ref().and_then(|movie| movie.id),
+                path: r.path,
+                quality: r.quality,
+                languages: r.languages,
+                release_group: r.release_group,
+                download_id: Some(Some(download_id.to_owned())),
+                indexer_flags: r.indexer_flags,
+                ..Default::default()
+            })
+            .collect();
+        let params = ApiV3CommandPostParams {
+            radarr_command_resource: Some(RadarrCommandResource {
+                name: Some(Some("ManualImport".to_owned())),
+                files: Some(Some(files)),
+                import_mode: Some(Some("auto".to_owned())),
+                ..Default::default()
+            }),
+        };
+        let command = self
+            .api
+            .command_api()
+            .api_v3_command_post(params)
+            .await
+            .map_err(|e| anyhow!("Could not start ManualImport command: {e}"))?;
+        Ok(command.id)
+    }
+
+    async fn command_running(&self, id: i32) -> Result<bool> {
+        let command = self
+            .api
+            .command_api()
+            .api_v3_command_id_get(ApiV3CommandIdGetParams { id })
+            .await
+            .map_err(|e| anyhow!("Could not get command {id}: {e}"))?;
+        Ok(matches!(
+            command.status,
+            Some(RadarrCommandStatus::Queued | RadarrCommandStatus::Started)
+        ))
+    }
 }
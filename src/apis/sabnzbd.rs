@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde::{Deserialize, de::DeserializeOwned};
+use time::OffsetDateTime;
+use url::Url;
+
+use crate::{
+    apis::{
+        UsenetAPIInterface, delete_job_folders, endpoint,
+        types::{UsenetFailure, UsenetJob},
+    },
+    config::SabnzbdConfig,
+};
+
+#[derive(Deserialize)]
+struct VersionResponse {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct QueueResponse {
+    queue: Queue,
+}
+
+#[derive(Deserialize)]
+struct Queue {
+    slots: Vec<QueueSlot>,
+}
+
+#[derive(Deserialize)]
+struct QueueSlot {
+    nzo_id: String,
+    filename: String,
+    #[serde(default)]
+    cat: String,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct HistoryResponse {
+    history: History,
+}
+
+#[derive(Deserialize)]
+struct History {
+    slots: Vec<HistorySlot>,
+}
+
+#[derive(Deserialize)]
+struct HistorySlot {
+    nzo_id: String,
+    name: String,
+    #[serde(default)]
+    category: String,
+    status: String,
+    #[serde(default)]
+    fail_message: String,
+    storage: Option<String>,
+    completed: Option<i64>,
+}
+
+/// Failure announced by the labels SABnzbd puts on queued jobs, which are
+/// only recognized in English.
+fn queue_failure(labels: &[String]) -> Option<UsenetFailure> {
+    labels.iter().find_map(|label| match label.as_str() {
+        "ENCRYPTED" => Some(UsenetFailure::Encrypted),
+        "INCOMPLETE" => Some(UsenetFailure::MissingArticles),
+        _ => None,
+    })
+}
+
+/// Classifies the `fail_message` of a failed history job.
+fn history_failure(fail_message: &str) -> UsenetFailure {
+    let message = fail_message.to_lowercase();
+    // Checked first, as unpacking fails for archives needing a password
+    if message.contains("password") || message.contains("encrypt") {
+        UsenetFailure::Encrypted
+    } else if message.contains("repair failed") || message.contains("repair blocks") {
+        UsenetFailure::RepairFailed
+    } else if message.contains("cannot be completed") || message.contains("missing articles") {
+        UsenetFailure::MissingArticles
+    } else if message.contains("unpack") {
+        UsenetFailure::UnpackFailed
+    } else {
+        UsenetFailure::Other
+    }
+}
+
+impl From<QueueSlot> for UsenetJob {
+    fn from(slot: QueueSlot) -> Self {
+        let failure = queue_failure(&slot.labels);
+        UsenetJob {
+            failure,
+            fail_message: failure.map(|_| slot.labels.join(", ")),
+            id: slot.nzo_id.clone(),
+            download_id: slot.nzo_id,
+            name: slot.filename,
+            category: slot.cat,
+            finished: false,
+            path: None,
+            completed_at: None,
+        }
+    }
+}
+
+impl From<HistorySlot> for UsenetJob {
+    fn from(slot: HistorySlot) -> Self {
+        // Jobs being verified, repaired or unpacked are in the history too
+        let failed = slot.status == "Failed";
+        let finished = failed || slot.status == "Completed";
+        UsenetJob {
+            failure: failed.then(|| history_failure(&slot.fail_message)),
+            fail_message: failed.then_some(slot.fail_message),
+            id: slot.nzo_id.clone(),
+            download_id: slot.nzo_id,
+            name: slot.name,
+            category: slot.category,
+            finished,
+            path: slot.storage.filter(|storage| !storage.is_empty()),
+            completed_at: slot
+                .completed
+                .and_then(|completed| OffsetDateTime::from_unix_timestamp(completed).ok()),
+        }
+    }
+}
+
+pub struct SabnzbdAPI {
+    client: reqwest::Client,
+    host: Url,
+    api_key: String,
+    download_root: Option<PathBuf>,
+}
+
+impl SabnzbdAPI {
+    pub fn new(config: &SabnzbdConfig) -> Self {
+        SabnzbdAPI {
+            client: reqwest::Client::new(),
+            host: config.host.clone(),
+            api_key: config.api_key.clone(),
+            download_root: config.download_root.clone(),
+        }
+    }
+
+    /// Calls the `mode` of the API. SABnzbd reports errors with a success
+    /// status and an `error` field, so that field is checked first.
+    async fn call<T: DeserializeOwned>(&self, mode: &str, params: &[(&str, &str)]) -> Result<T> {
+        let url = endpoint(&self.host, "api")?;
+        let response: serde_json::Value = self
+            .client
+            .get(url)
+            .query(&[
+                ("mode", mode),
+                ("output", "json"),
+                ("apikey", self.api_key.as_str()),
+            ])
+            .query(params)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Could not call SABnzbd '{mode}'"))?
+            .json()
+            .await
+            .with_context(|| format!("Invalid response to SABnzbd '{mode}'"))?;
+        if let Some(error) = response.get("error").and_then(|error| error.as_str()) {
+            bail!("SABnzbd '{mode}' failed: {error}");
+        }
+        serde_json::from_value(response)
+            .with_context(|| format!("Invalid response to SABnzbd '{mode}'"))
+    }
+}
+
+#[async_trait]
+impl UsenetAPIInterface for SabnzbdAPI {
+    async fn get_version(&self) -> Result<String> {
+        let response: VersionResponse = self.call("version", &[]).await?;
+        Ok(response.version)
+    }
+
+    async fn get_queue(&self) -> Result<Vec<UsenetJob>> {
+        let response: QueueResponse = self.call("queue", &[]).await?;
+        Ok(response
+            .queue
+            .slots
+            .into_iter()
+            .map(UsenetJob::from)
+            .collect())
+    }
+
+    async fn get_history(&self) -> Result<Vec<UsenetJob>> {
+        let response: HistoryResponse = self.call("history", &[]).await?;
+        Ok(response
+            .history
+            .slots
+            .into_iter()
+            .map(UsenetJob::from)
+            .collect())
+    }
+
+    async fn delete_history(&self, jobs: Vec<&UsenetJob>, delete_files: bool) -> Result<()> {
+        if jobs.is_empty() {
+            return Ok(());
+        }
+        let ids: Vec<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+        let _: serde_json::Value = self
+            .call(
+                "history",
+                &[
+                    ("name", "delete"),
+                    ("value", &ids.join(",")),
+                    ("del_files", if delete_files { "1" } else { "0" }),
+                ],
+            )
+            .await?;
+        // SABnzbd only deletes the files of failed jobs, so the folders of
+        // completed ones are deleted here, once their history is gone
+        if delete_files {
+            let completed: Vec<&UsenetJob> = jobs
+                .iter()
+                .copied()
+                .filter(|job| job.failure.is_none())
+                .collect();
+            delete_job_folders(self.download_root.as_deref(), &completed).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── failures ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn queue_failure_from_labels() {
+        let labels = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            queue_failure(&labels(&["DUPLICATE", "ENCRYPTED"])),
+            Some(UsenetFailure::Encrypted)
+        );
+        assert_eq!(
+            queue_failure(&labels(&["INCOMPLETE"])),
+            Some(UsenetFailure::MissingArticles)
+        );
+        assert_eq!(queue_failure(&labels(&["DUPLICATE"])), None);
+    }
+
+    #[test]
+    fn history_failure_from_message() {
+        assert_eq!(
+            history_failure("Aborted, cannot be completed - https://sabnzbd.org/not-complete"),
+            UsenetFailure::MissingArticles
+        );
+        assert_eq!(
+            history_failure("Repair failed, not enough repair blocks (12 short)"),
+            UsenetFailure::RepairFailed
+        );
+        assert_eq!(
+            history_failure("Unpacking failed, archive requires a password"),
+            UsenetFailure::Encrypted
+        );
+        assert_eq!(
+            history_failure("Aborted, encryption detected"),
+            UsenetFailure::Encrypted
+        );
+        assert_eq!(
+            history_failure("Unpacking failed, write error or disk is full?"),
+            UsenetFailure::UnpackFailed
+        );
+        assert_eq!(history_failure("Something else"), UsenetFailure::Other);
+    }
+
+    // ── responses ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn queue_slots_become_jobs() {
+        let response: QueueResponse = serde_json::from_str(
+            r#"{"queue": {"slots": [{
+                "nzo_id": "SABnzbd_nzo_abc",
+                "filename": "Some.Show.S01E01",
+                "cat": "tv",
+                "labels": ["ENCRYPTED"],
+                "percentage": "10"
+            }]}}"#,
+        )
+        .unwrap();
+        let job = UsenetJob::from(response.queue.slots.into_iter().next().unwrap());
+        assert_eq!(job.download_id, "SABnzbd_nzo_abc");
+        assert_eq!(job.category, "tv");
+        assert!(!job.finished);
+        assert_eq!(job.failure, Some(UsenetFailure::Encrypted));
+    }
+
+    #[test]
+    fn history_slots_become_jobs() {
+        let response: HistoryResponse = serde_json::from_str(
+            r#"{"history": {"slots": [
+                {
+                    "nzo_id": "SABnzbd_nzo_abc",
+                    "name": "Some.Show.S01E01",
+                    "category": "tv",
+                    "status": "Failed",
+                    "fail_message": "Repair failed, not enough repair blocks (3 short)",
+                    "storage": "/downloads/incomplete/Some.Show.S01E01",
+                    "completed": 1700000000
+                },
+                {
+                    "nzo_id": "SABnzbd_nzo_def",
+                    "name": "Other.Show.S01E01",
+                    "category": "tv",
+                    "status": "Completed",
+                    "fail_message": "",
+                    "storage": "",
+                    "completed": 1700000000
+                }
+            ]}}"#,
+        )
+        .unwrap();
+        let jobs: Vec<UsenetJob> = response
+            .history
+            .slots
+            .into_iter()
+            .map(UsenetJob::from)
+            .collect();
+        assert!(jobs[0].finished);
+        assert_eq!(jobs[0].failure, Some(UsenetFailure::RepairFailed));
+        assert_eq!(
+            jobs[0].path.as_deref(),
+            Some("/downloads/incomplete/Some.Show.S01E01")
+        );
+        assert_eq!(
+            jobs[0].completed_at,
+            OffsetDateTime::from_unix_timestamp(1_700_000_000).ok()
+        );
+        assert_eq!(jobs[1].failure, None);
+        assert_eq!(jobs[1].fail_message, None);
+        assert_eq!(jobs[1].path, None);
+    }
+}
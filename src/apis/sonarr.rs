@@ -3,13 +3,19 @@ use async_trait::async_trait;
 use sonarr::{
     apis::{
         Api as _, ApiClient,
+        command_api::{ApiV3CommandIdGetParams, ApiV3CommandPostParams},
         configuration::{ApiKey, Configuration},
+        episode_api::ApiV3EpisodeMonitorPutParams,
+        manual_import_api::ApiV3ManualimportGetParams,
         queue_api::{ApiV3QueueBulkDeleteParams, ApiV3QueueGetParams},
+        series_editor_api::ApiV3SeriesEditorPutParams,
     },
     models::{
-        SonarrHealthCheckResult, SonarrQueueBulkResource, SonarrQueueResource, SonarrQueueStatus,
-        SonarrSystemResource, SonarrTrackedDownloadState, SonarrTrackedDownloadStatus,
-        SonarrTrackedDownloadStatusMessage,
+        SonarrCommandResource, SonarrCommandStatus, SonarrEpisodesMonitoredResource,
+        SonarrHealthCheckResult, SonarrManualImportReprocessResource, SonarrManualImportResource,
+        SonarrQueueBulkResource, SonarrQueueResource, SonarrQueueStatus,
+        SonarrSeriesEditorResource, SonarrSystemResource, SonarrTrackedDownloadState,
+        SonarrTrackedDownloadStatus, SonarrTrackedDownloadStatusMessage,
     },
 };
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
@@ -18,8 +24,8 @@ use crate::{
     apis::{
         SonarrAndRadarrAPIInterface,
         types::{
-            QueueResource, QueueStatus, SystemStatus, TrackedDownloadState, TrackedDownloadStatus,
-            TrackedDownloadStatusMessage,
+            ImportCandidate, MediaItem, QueueResource, QueueStatus, SystemStatus,
+            TrackedDownloadState, TrackedDownloadStatus, TrackedDownloadStatusMessage,
         },
     },
     config::SonarrConfig,
@@ -103,6 +109,10 @@ impl TryFrom<SonarrQueueResource> for QueueResource {
             size: r.size.unwrap_or_default() as i64,
             title: r.title.flatten(),
             download_id: r.download_id.flatten(),
+            series_id: r.series_id.flatten(),
+            episode_id: r.episode_id.flatten(),
+            episode_ids: r.episode_id.flatten().into_iter().collect(),
+            movie_id: None,
             status: r
                 .status
                 .map(QueueStatus::from)
@@ -128,11 +138,51 @@ impl TryFrom<SonarrQueueResource> for QueueResource {
     }
 }
 
+impl From<&SonarrManualImportResource> for ImportCandidate {
+    fn from(r: &SonarrManualImportResource) -> Self {
+        ImportCandidate {
+            path: r.path.clone().flatten().unwrap_or_default(),
+            series_id: r.series.as_ref().and_then(|series| series.id),
+            episode_ids: r
+                .episodes
+                .iter()
+                .flatten()
+                .flatten()
+                .filter_map(|episode| episode.id)
+                .collect(),
+            movie_id: None,
+            rejections: r
+                .rejections
+                .iter()
+                .flatten()
+                .flatten()
+                .filter_map(|rejection| rejection.reason.clone().flatten())
+                .collect(),
+        }
+    }
+}
+
 pub struct SonarrAPI {
     api: ApiClient,
 }
 
 impl SonarrAPI {
+    async fn get_manual_import(
+        &self,
+        download_id: &str,
+    ) -> Result<Vec<SonarrManualImportResource>> {
+        let params = ApiV3ManualimportGetParams {
+            download_id: Some(download_id.to_owned()),
+            filter_existing_files: Some(true),
+            ..Default::default()
+        };
+        self.api
+            .manual_import_api()
+            .api_v3_manualimport_get(params)
+            .await
+            .map_err(|e| anyhow!("Could not list files of download '{download_id}': {e}"))
+    }
+
     pub fn new(app_config: &SonarrConfig) -> Self {
         let mut config = Configuration::default();
         config.base_path = app_config
@@ -180,7 +230,8 @@ impl SonarrAndRadarrAPIInterface for SonarrAPI {
             .total_records
             .ok_or(anyhow!("Error getting queue size"))?;
 
-        self.api
+        let mut queue = self
+            .api
             .queue_api()
             .api_v3_queue_get(
                 ApiV3QueueGetParams::builder()
@@ -193,7 +244,9 @@ impl SonarrAndRadarrAPIInterface for SonarrAPI {
             .unwrap_or_default()
             .into_iter()
             .map(QueueResource::try_from)
-            .collect()
+            .collect::<Result<Vec<_>>>()?;
+        QueueResource::group_episodes(&mut queue);
+        Ok(queue)
     }
 
     async fn queue_bulk_delete(
@@ -219,4 +272,125 @@ impl SonarrAndRadarrAPIInterface for SonarrAPI {
             .api_v3_queue_bulk_delete(params)
             .await?)
     }
+
+    async fn search(&self, ids: Vec<i32>) -> Result<()> {
+        let params = ApiV3CommandPostParams {
+            sonarr_command_resource: Some(SonarrCommandResource {
+                name: Some(Some("EpisodeSearch".to_owned())),
+                episode_ids: Some(Some(ids)),
+                ..Default::default()
+            }),
+        };
+        self.api
+            .command_api()
+            .api_v3_command_post(params)
+            .await
+            .map_err(|e| anyhow!("Could not start EpisodeSearch command: {e}"))?;
+        Ok(())
+    }
+
+    async fn unmonitor(&self, items: Vec<MediaItem>) -> Result<()> {
+        let mut series_ids = Vec::new();
+        let mut episode_ids = Vec::new();
+        for item in items {
+            match item {
+                MediaItem::Series(id) => series_ids.push(id),
+                MediaItem::Episode(id) => episode_ids.push(id),
+                MediaItem::Movie(id) => return Err(anyhow!("Sonarr has no movie {id}")),
+            }
+        }
+        if !episode_ids.is_empty() {
+            let params = ApiV3EpisodeMonitorPutParams {
+                include_images: None,
+                sonarr_episodes_monitored_resource: Some(SonarrEpisodesMonitoredResource {
+                    episode_ids: Some(Some(episode_ids)),
+                    monitored: Some(false),
+                }),
+            };
+            self.api
+                .episode_api()
+                .api_v3_episode_monitor_put(params)
+                .await
+                .map_err(|e| anyhow!("Could not unmonitor episodes: {e}"))?;
+        }
+        if !series_ids.is_empty() {
+            let params = ApiV3SeriesEditorPutParams {
+                sonarr_series_editor_resource: Some(SonarrSeriesEditorResource {
+                    series_ids: Some(Some(series_ids)),
+                    monitored: Some(Some(false)),
+                    ..Default::default()
+                }),
+            };
+            self.api
+                .series_editor_api()
+                .api_v3_series_editor_put(params)
+                .await
+                .map_err(|e| anyhow!("Could not unmonitor series: {e}"))?;
+        }
+        Ok(())
+    }
+
+    async fn get_import_candidates(&self, download_id: &str) -> Result<Vec<ImportCandidate>> {
+        Ok(self
+            .get_manual_import(download_id)
+            .await?
+            .iter()
+            .map(ImportCandidate::from)
+            .collect())
+    }
+
+    async fn manual_import(&self, download_id: &str, paths: Vec<String>) -> Result<Option<i32>> {
+        let files = self
+            .get_manual_import(download_id)
+            .await?
+            .into_iter()
+            .filter(|r| {
+                r.path
+                    .clone()
+                    .flatten()
+                    .is_some_and(|path| paths.contains(&path))
+            })
+            .map(|r| SonarrManualImportReprocessResource {
+                series_id: r.series.as_ref().and_then(|series| series.id),
+                episode_ids: Some(Some(ImportCandidate::from(&r).episode_ids)),
+                path: r.path,
+                season_number: r.season_number,
+                quality: r.quality,
+                languages: r.languages,
+                release_group: r.release_group,
+                download_id: Some(Some(download_id.to_owned())),
+                indexer_flags: r.indexer_flags,
+                release_type: r.release_type,
+                ..Default::default()
+            })
+            .collect();
+        let params = ApiV3CommandPostParams {
+            sonarr_command_resource: Some(SonarrCommandResource {
+                name: Some(Some("ManualImport".to_owned())),
+                files: Some(Some(files)),
+                import_mode: Some(Some("auto".to_owned())),
+                ..Default::default()
+            }),
+        };
+        let command = self
+            .api
+            .command_api()
+            .api_v3_command_post(params)
+            .await
+            .map_err(|e| anyhow!("Could not start ManualImport command: {e}"))?;
+        Ok(command.id)
+    }
+
+    async fn command_running(&self, id: i32) -> Result<bool> {
+        let command = self
+            .api
+            .command_api()
+            .api_v3_command_id_get(ApiV3CommandIdGetParams { id })
+            .await
+            .map_err(|e| anyhow!("Could not get command {id}: {e}"))?;
+        Ok(matches!(
+            command.status,
+            Some(SonarrCommandStatus::Queued | SonarrCommandStatus::Started)
+        ))
+    }
 }
@@ -1,6 +1,9 @@
+use std::{collections::HashMap, fmt};
+
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy, Serialize, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum QueueStatus {
     Unknown,
     Queued,
@@ -14,7 +17,7 @@ pub enum QueueStatus {
     Fallback,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy, Serialize, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum TrackedDownloadState {
     Downloading,
     ImportBlocked,
@@ -40,6 +43,68 @@ pub struct TrackedDownloadStatusMessage {
     pub messages: Vec<String>,
 }
 
+/// Something the *arr can search for or monitor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum MediaItem {
+    Series(i32),
+    Episode(i32),
+    Movie(i32),
+}
+
+/// File of a finished download as identified by the *arr's manual import.
+#[derive(Clone, Debug)]
+pub struct ImportCandidate {
+    pub path: String,
+    pub series_id: Option<i32>,
+    pub episode_ids: Vec<i32>,
+    pub movie_id: Option<i32>,
+    /// Why the *arr does not import the file on its own
+    pub rejections: Vec<String>,
+}
+
+/// Why SABnzbd or NZBGet failed a download, or is going to.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsenetFailure {
+    MissingArticles,
+    RepairFailed,
+    /// The archive is encrypted or needs a password
+    Encrypted,
+    UnpackFailed,
+    Other,
+}
+
+impl fmt::Display for UsenetFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UsenetFailure::MissingArticles => "missing_articles",
+            UsenetFailure::RepairFailed => "repair_failed",
+            UsenetFailure::Encrypted => "encrypted",
+            UsenetFailure::UnpackFailed => "unpack_failed",
+            UsenetFailure::Other => "other",
+        })
+    }
+}
+
+/// A download of SABnzbd or NZBGet, either queued or in the history.
+#[derive(Clone, Debug)]
+pub struct UsenetJob {
+    /// Id the client knows the job by
+    pub id: String,
+    /// Id the *arrs know the download by
+    pub download_id: String,
+    pub name: String,
+    pub category: String,
+    /// Whether the client is done with the job, including post-processing
+    pub finished: bool,
+    pub failure: Option<UsenetFailure>,
+    /// How the client describes the failure
+    pub fail_message: Option<String>,
+    /// Folder holding the files of a finished job
+    pub path: Option<String>,
+    pub completed_at: Option<OffsetDateTime>,
+}
+
 #[derive(Clone)]
 pub struct SystemStatus {
     pub start_time: OffsetDateTime,
@@ -52,6 +117,15 @@ pub struct QueueResource {
     pub size: i64,
     pub title: Option<String>,
     pub download_id: Option<String>,
+    /// Series the item was grabbed for, only set by Sonarr
+    pub series_id: Option<i32>,
+    /// Episode the item was grabbed for, only set by Sonarr
+    pub episode_id: Option<i32>,
+    /// Every episode of the download, which Sonarr queues once per episode
+    /// for season packs
+    pub episode_ids: Vec<i32>,
+    /// Movie the item was grabbed for, only set by Radarr
+    pub movie_id: Option<i32>,
     pub status: QueueStatus,
     pub tracked_download_status: TrackedDownloadStatus,
     pub tracked_download_state: TrackedDownloadState,
@@ -60,3 +134,30 @@ pub struct QueueResource {
     pub sizeleft: i64,
     pub error_message: Option<String>,
 }
+
+impl QueueResource {
+    /// Gives each item of `queue` the episodes of every item that shares its
+    /// download id.
+    pub fn group_episodes(queue: &mut [QueueResource]) {
+        let mut episodes: HashMap<String, Vec<i32>> = HashMap::new();
+        for resource in queue.iter() {
+            if let (Some(download_id), Some(episode_id)) =
+                (&resource.download_id, resource.episode_id)
+            {
+                episodes
+                    .entry(download_id.to_lowercase())
+                    .or_default()
+                    .push(episode_id);
+            }
+        }
+        for resource in queue {
+            if let Some(ids) = resource
+                .download_id
+                .as_ref()
+                .and_then(|download_id| episodes.get(&download_id.to_lowercase()))
+            {
+                resource.episode_ids.clone_from(ids);
+            }
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use std::fmt::Write;
+
+use chrono::TimeZone;
+use serde::Serialize;
+use thiserror::Error;
+use time::{OffsetDateTime, UtcOffset, format_description::well_known::Rfc3339};
+
+use crate::{
+    history::{HistoryFilter, RunRecord},
+    tasks::{ItemAction, TaskReport},
+};
+
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum CliError {
+    #[error("unknown command '{0}'")]
+    UnknownCommand(String),
+
+    #[error("unknown option '{0}'")]
+    UnknownOption(String),
+
+    #[error("missing value for option '{0}'")]
+    MissingValue(String),
+
+    #[error("invalid value '{value}' for option '{option}'")]
+    InvalidValue { option: String, value: String },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CliCommand {
+    /// Run the scheduler (the default when no command is given)
+    Run,
+    /// Print recorded task runs
+    History(HistoryFilter),
+    /// Query the health endpoint of a running instance, `/healthz` when
+    /// `live` is set and `/readyz` otherwise
+    Healthcheck { live: bool },
+    /// Run every configured task once as a dry run and print what each
+    /// would do
+    DryRun { report: ReportFormat },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Result of a single task of a `dry-run` command.
+#[derive(Serialize, Debug)]
+pub struct DryRunOutcome {
+    pub task: String,
+    pub error: Option<String>,
+    pub report: Option<TaskReport>,
+}
+
+/// Parses a date given as `YYYY-MM-DD` (local midnight) or as an RFC 3339 timestamp.
+/// When `end_of_day` is set a plain date refers to the following midnight, so
+/// `--until 2024-01-31` includes the whole day.
+fn parse_date(value: &str, end_of_day: bool) -> Option<OffsetDateTime> {
+    if let Ok(date) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Some(date);
+    }
+    let mut date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    if end_of_day {
+        date = date.succ_opt()?;
+    }
+    let midnight = chrono::Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()?;
+    OffsetDateTime::from_unix_timestamp(midnight.timestamp()).ok()
+}
+
+fn parse_history<I>(mut args: I) -> Result<CliCommand, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut filter = HistoryFilter::default();
+    while let Some(option) = args.next() {
+        let value = match option.as_str() {
+            "--task" | "--since" | "--until" | "--limit" => args
+                .next()
+                .ok_or_else(|| CliError::MissingValue(option.clone()))?,
+            _ => return Err(CliError::UnknownOption(option)),
+        };
+        let invalid = || CliError::InvalidValue {
+            option: option.clone(),
+            value: value.clone(),
+        };
+        match option.as_str() {
+            "--task" => filter.task = Some(value.clone()),
+            "--since" => filter.since = Some(parse_date(&value, false).ok_or_else(invalid)?),
+            "--until" => filter.until = Some(parse_date(&value, true).ok_or_else(invalid)?),
+            "--limit" => filter.limit = value.parse().map_err(|_| invalid())?,
+            _ => unreachable!(),
+        }
+    }
+    Ok(CliCommand::History(filter))
+}
+
+/// Parses the command line arguments, excluding the program name.
+pub fn parse_args<I>(args: I) -> Result<CliCommand, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None | Some("run") => match args.next() {
+            None => Ok(CliCommand::Run),
+            Some(option) => Err(CliError::UnknownOption(option)),
+        },
+        Some("history") => parse_history(args),
+        Some("healthcheck") => {
+            let mut live = false;
+            for option in args {
+                match option.as_str() {
+                    "--live" => live = true,
+                    _ => return Err(CliError::UnknownOption(option)),
+                }
+            }
+            Ok(CliCommand::Healthcheck { live })
+        }
+        Some("dry-run") => {
+            let mut report = ReportFormat::default();
+            while let Some(option) = args.next() {
+                match option.as_str() {
+                    "--report" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| CliError::MissingValue(option.clone()))?;
+                        report = match value.as_str() {
+                            "text" => ReportFormat::Text,
+                            "json" => ReportFormat::Json,
+                            _ => return Err(CliError::InvalidValue { option, value }),
+                        };
+                    }
+                    _ => return Err(CliError::UnknownOption(option)),
+                }
+            }
+            Ok(CliCommand::DryRun { report })
+        }
+        Some(command) => Err(CliError::UnknownCommand(command.to_owned())),
+    }
+}
+
+fn format_date(date: OffsetDateTime) -> String {
+    let date = match UtcOffset::current_local_offset() {
+        Ok(offset) => date.to_offset(offset),
+        Err(_) => date,
+    };
+    date.replace_nanosecond(0)
+        .unwrap_or(date)
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| date.to_string())
+        .replacen('T', " ", 1)
+}
+
+fn write_actions(output: &mut String, actions: &[ItemAction]) {
+    for action in actions {
+        let _ = write!(
+            output,
+            "    {} {} [{}]",
+            action.action, action.name, action.id
+        );
+        if let Some(reason) = &action.reason {
+            let _ = write!(output, ": {reason}");
+        }
+        output.push('\n');
+    }
+}
+
+/// Formats task runs as a human readable listing.
+pub fn format_runs(runs: &[RunRecord]) -> String {
+    if runs.is_empty() {
+        return "No task runs recorded\n".to_owned();
+    }
+    let mut output = String::new();
+    for run in runs {
+        let duration = humantime::format_duration(
+            (run.finished_at - run.started_at)
+                .try_into()
+                .unwrap_or_default(),
+        );
+        let outcome = match &run.error {
+            None => "ok".to_owned(),
+            Some(error) => format!("failed: {error}"),
+        };
+        let _ = writeln!(
+            output,
+            "{} {} ({}) {}, {} item(s) affected",
+            format_date(run.started_at),
+            run.task,
+            duration,
+            outcome,
+            run.actions.len()
+        );
+        write_actions(&mut output, &run.actions);
+    }
+    output
+}
+
+/// Formats the outcome of a `dry-run` command as a human readable listing.
+pub fn format_dry_run(outcomes: &[DryRunOutcome]) -> String {
+    if outcomes.is_empty() {
+        return "No tasks configured\n".to_owned();
+    }
+    let mut output = String::new();
+    for outcome in outcomes {
+        let actions = outcome.report.as_ref().map_or(&[][..], TaskReport::actions);
+        match &outcome.error {
+            None => {
+                let _ = writeln!(
+                    output,
+                    "{} would affect {} item(s)",
+                    outcome.task,
+                    actions.len()
+                );
+            }
+            Some(error) => {
+                let _ = writeln!(output, "{} failed: {error}", outcome.task);
+            }
+        }
+        write_actions(&mut output, actions);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    // ── parse_args ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn no_arguments_runs_scheduler() {
+        assert_eq!(parse_args(args(&[])), Ok(CliCommand::Run));
+        assert_eq!(parse_args(args(&["run"])), Ok(CliCommand::Run));
+    }
+
+    #[test]
+    fn unknown_command_is_rejected() {
+        assert_eq!(
+            parse_args(args(&["frobnicate"])),
+            Err(CliError::UnknownCommand("frobnicate".to_owned()))
+        );
+    }
+
+    #[test]
+    fn history_defaults() {
+        assert_eq!(
+            parse_args(args(&["history"])),
+            Ok(CliCommand::History(HistoryFilter::default()))
+        );
+    }
+
+    #[test]
+    fn history_with_filters() {
+        let Ok(CliCommand::History(filter)) = parse_args(args(&[
+            "history",
+            "--task",
+            "cleanup",
+            "--since",
+            "2024-01-01T00:00:00Z",
+            "--until",
+            "2024-01-02T00:00:00Z",
+            "--limit",
+            "10",
+        ])) else {
+            panic!("expected history command");
+        };
+        assert_eq!(filter.task.as_deref(), Some("cleanup"));
+        assert_eq!(
+            filter.since,
+            Some(OffsetDateTime::parse("2024-01-01T00:00:00Z", &Rfc3339).unwrap())
+        );
+        assert_eq!(
+            filter.until,
+            Some(OffsetDateTime::parse("2024-01-02T00:00:00Z", &Rfc3339).unwrap())
+        );
+        assert_eq!(filter.limit, 10);
+    }
+
+    #[test]
+    fn history_until_plain_date_includes_whole_day() {
+        let Ok(CliCommand::History(filter)) = parse_args(args(&[
+            "history",
+            "--since",
+            "2024-01-01",
+            "--until",
+            "2024-01-01",
+        ])) else {
+            panic!("expected history command");
+        };
+        let span = filter.until.unwrap() - filter.since.unwrap();
+        assert_eq!(span, time::Duration::days(1));
+    }
+
+    #[test]
+    fn history_missing_value() {
+        assert_eq!(
+            parse_args(args(&["history", "--task"])),
+            Err(CliError::MissingValue("--task".to_owned()))
+        );
+    }
+
+    #[test]
+    fn history_invalid_values() {
+        assert_eq!(
+            parse_args(args(&["history", "--since", "yesterday"])),
+            Err(CliError::InvalidValue {
+                option: "--since".to_owned(),
+                value: "yesterday".to_owned()
+            })
+        );
+        assert!(matches!(
+            parse_args(args(&["history", "--limit", "-1"])),
+            Err(CliError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn history_unknown_option() {
+        assert_eq!(
+            parse_args(args(&["history", "--verbose"])),
+            Err(CliError::UnknownOption("--verbose".to_owned()))
+        );
+    }
+
+    #[test]
+    fn healthcheck_defaults_to_readiness() {
+        assert_eq!(
+            parse_args(args(&["healthcheck"])),
+            Ok(CliCommand::Healthcheck { live: false })
+        );
+        assert_eq!(
+            parse_args(args(&["healthcheck", "--live"])),
+            Ok(CliCommand::Healthcheck { live: true })
+        );
+        assert_eq!(
+            parse_args(args(&["healthcheck", "--ready"])),
+            Err(CliError::UnknownOption("--ready".to_owned()))
+        );
+    }
+
+    // ── format_runs ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn format_runs_empty() {
+        assert_eq!(format_runs(&[]), "No task runs recorded\n");
+    }
+
+    #[test]
+    fn format_runs_lists_outcome_and_actions() {
+        let started_at = OffsetDateTime::now_utc();
+        let runs = vec![
+            RunRecord {
+                task: "cleanup".to_owned(),
+                started_at,
+                finished_at: started_at + Duration::from_secs(2),
+                error: None,
+                actions: vec![ItemAction {
+                    id: "abc".to_owned(),
+                    name: "Some.Torrent".to_owned(),
+                    action: "delete".to_owned(),
+                    reason: None,
+                }],
+            },
+            RunRecord {
+                task: "retry".to_owned(),
+                started_at,
+                finished_at: started_at,
+                error: Some("connection refused".to_owned()),
+                actions: vec![],
+            },
+        ];
+        let output = format_runs(&runs);
+        assert!(output.contains("cleanup (2s) ok, 1 item(s) affected"));
+        assert!(output.contains("    delete Some.Torrent [abc]"));
+        assert!(output.contains("retry (0s) failed: connection refused, 0 item(s) affected"));
+    }
+
+    // ── dry-run ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn dry_run_report_format() {
+        assert_eq!(
+            parse_args(args(&["dry-run"])),
+            Ok(CliCommand::DryRun {
+                report: ReportFormat::Text
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["dry-run", "--report", "json"])),
+            Ok(CliCommand::DryRun {
+                report: ReportFormat::Json
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["dry-run", "--report", "xml"])),
+            Err(CliError::InvalidValue {
+                option: "--report".to_owned(),
+                value: "xml".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["dry-run", "--report"])),
+            Err(CliError::MissingValue("--report".to_owned()))
+        );
+    }
+
+    #[test]
+    fn format_dry_run_lists_actions_with_reasons() {
+        let outcomes = vec![
+            DryRunOutcome {
+                task: "retry".to_owned(),
+                error: None,
+                report: Some(TaskReport::Retry {
+                    strikes: vec![],
+                    verdicts: vec![],
+                    actions: vec![ItemAction {
+                        id: "abc".to_owned(),
+                        name: "Some.Show.S01E01".to_owned(),
+                        action: "dry_run".to_owned(),
+                        reason: Some("stalled (5/5 strikes)".to_owned()),
+                    }],
+                }),
+            },
+            DryRunOutcome {
+                task: "cleanup".to_owned(),
+                error: Some("connection refused".to_owned()),
+                report: None,
+            },
+        ];
+        let output = format_dry_run(&outcomes);
+        assert!(output.contains("retry would affect 1 item(s)"));
+        assert!(output.contains("    dry_run Some.Show.S01E01 [abc]: stalled (5/5 strikes)"));
+        assert!(output.contains("cleanup failed: connection refused"));
+        assert_eq!(format_dry_run(&[]), "No tasks configured\n");
+    }
+}
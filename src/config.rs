@@ -1,48 +1,16 @@
-use std::{str::FromStr, time::Duration};
+use std::{net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 
-use chrono::TimeZone;
+use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
 use url::Url;
 
-#[derive(Clone, Serialize, Debug, PartialEq)]
-pub struct Schedule(String);
+use crate::apis::types::UsenetFailure;
 
-impl Schedule {
-    pub fn next_date(&self, from: time::OffsetDateTime) -> Option<time::OffsetDateTime> {
-        let schedule = croner::Cron::from_str(&self.0).ok();
-        let from_chrono = chrono::Local
-            .timestamp_opt(from.unix_timestamp(), 0)
-            .single();
-        schedule
-            .zip(from_chrono)
-            .and_then(|(schedule, from_chrono)| {
-                schedule.find_next_occurrence(&from_chrono, false).ok()
-            })
-            .and_then(|next_chrono: chrono::DateTime<chrono::Local>| {
-                let offset_secs = next_chrono.offset().local_minus_utc();
-                let offset = time::UtcOffset::from_whole_seconds(offset_secs.into());
-                let dt = time::OffsetDateTime::from_unix_timestamp(next_chrono.timestamp()).ok();
-                offset.ok().zip(dt).map(|(offset, dt)| dt.to_offset(offset))
-            })
-    }
-}
-
-impl std::str::FromStr for Schedule {
-    type Err = croner::errors::CronError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        croner::Cron::from_str(s).map(|_| Schedule(s.to_owned()))
-    }
-}
+mod expression;
+mod schedule;
 
-impl<'de> Deserialize<'de> for Schedule {
-    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        String::deserialize(deserializer).and_then(|s| {
-            Schedule::from_str(&s)
-                .map_err(|e| serde::de::Error::custom(format!("invalid schedule: {e}")))
-        })
-    }
-}
+pub use expression::{Expression, Field, Value};
+pub use schedule::Schedule;
 
 fn deserialize_string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
@@ -75,11 +43,18 @@ where
     deserializer.deserialize_any(StringOrVec)
 }
 
+fn default_max_concurrent_requests() -> usize {
+    8
+}
+
 #[derive(Clone, Deserialize, Debug)]
 pub struct QBittorrentConfig {
     pub username: String,
     pub password: String,
     pub host: Url,
+    /// Maximum number of per-torrent requests sent to qBittorrent at once
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -94,6 +69,74 @@ pub struct RadarrConfig {
     pub api_key: String,
 }
 
+#[derive(Clone, Deserialize, Debug)]
+pub struct SabnzbdConfig {
+    pub host: Url,
+    pub api_key: String,
+    /// Folder SABnzbd downloads into, as seen from here. The folders of
+    /// completed jobs are only deleted below it, and never when not set
+    pub download_root: Option<PathBuf>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct NzbgetConfig {
+    pub host: Url,
+    pub username: String,
+    pub password: String,
+    /// Folder NZBGet downloads into, as seen from here. The folders of jobs
+    /// are only deleted below it, and never when not set
+    pub download_root: Option<PathBuf>,
+}
+
+/// Amount of bytes, written either as a number or with a unit such as `50GB`.
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, PartialOrd)]
+pub struct ByteSize(pub u64);
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        expression::parse_size(s.trim())
+            .map(|bytes| ByteSize(bytes as u64))
+            .ok_or_else(|| format!("invalid size '{s}'"))
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u64),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(bytes) => Ok(ByteSize(bytes)),
+            Raw::Text(s) => ByteSize::from_str(&s).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Removes large torrents that are not seeding well, e.g. larger than 50GB
+/// with a ratio below 0.5 after seeding for 7 days.
+#[derive(Clone, Deserialize, Debug)]
+pub struct SizeRuleConfig {
+    pub larger_than: ByteSize,
+    pub ratio_below: Option<f64>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub seeding_time: Option<Duration>,
+}
+
+/// Which torrents to remove first when a tracker exceeds its seeding limits.
+#[derive(Clone, Deserialize, PartialEq, Default, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum EvictionOrder {
+    #[default]
+    LowestRatio,
+    Oldest,
+}
+
 fn default_hard_links_percentage() -> u64 {
     50
 }
@@ -107,11 +150,54 @@ pub enum TrackerIgnore {
     WhenHardLinked,
 }
 
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|pattern| {
+            Regex::new(&pattern)
+                .map_err(|e| serde::de::Error::custom(format!("invalid url_pattern: {e}")))
+        })
+        .transpose()
+}
+
+/// Matches `host` against a domain rule: an exact host, a parent domain of
+/// `host`, or a pattern where `*` matches any run of characters.
+fn host_matches(rule: &str, host: &str) -> bool {
+    let rule = rule.to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    if !rule.contains('*') {
+        return host == rule || host.ends_with(&format!(".{rule}"));
+    }
+
+    let mut parts = rule.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = host.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
 #[derive(Clone, Deserialize, Debug)]
+#[serde(remote = "Self")]
 pub struct TrackerConfig {
     pub name: String,
-    #[serde(deserialize_with = "deserialize_string_or_vec")]
+    /// Hosts of the tracker, which also match their subdomains and may
+    /// contain `*` wildcards
+    #[serde(deserialize_with = "deserialize_string_or_vec", default)]
     pub domain: Vec<String>,
+    /// Regular expression matched against the whole announce URL
+    #[serde(deserialize_with = "deserialize_regex", default)]
+    pub url_pattern: Option<Regex>,
     pub ratio: Option<f64>,
     #[serde(with = "humantime_serde::option", default)]
     pub seeding_time: Option<Duration>,
@@ -120,17 +206,154 @@ pub struct TrackerConfig {
     #[serde(default = "default_hard_links_percentage")]
     pub hard_links_percentage: u64,
     pub ignore: Option<TrackerIgnore>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub inactive_for: Option<Duration>,
+    pub size_rule: Option<SizeRuleConfig>,
+    /// Maximum number of torrents kept seeding on this tracker
+    pub max_torrents: Option<usize>,
+    /// Maximum total size of the torrents kept seeding on this tracker
+    pub max_total_size: Option<ByteSize>,
+    pub evict: Option<EvictionOrder>,
+}
+
+/// Rejects trackers that could never match a torrent.
+impl<'de> Deserialize<'de> for TrackerConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tracker = TrackerConfig::deserialize(deserializer)?;
+        if tracker.domain.is_empty() && tracker.url_pattern.is_none() {
+            return Err(serde::de::Error::custom(format!(
+                "tracker '{}' needs a domain or a url_pattern",
+                tracker.name
+            )));
+        }
+        Ok(tracker)
+    }
+}
+
+impl TrackerConfig {
+    /// Whether the announce `url` belongs to this tracker.
+    pub fn matches(&self, url: &Url) -> bool {
+        let host_matched = url
+            .host_str()
+            .is_some_and(|host| self.domain.iter().any(|rule| host_matches(rule, host)));
+        host_matched
+            || self
+                .url_pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.is_match(url.as_str()))
+    }
 }
 
+/// What to do with a torrent when a cleanup filter fails to evaluate it,
+/// from the most to the least permissive.
+#[derive(Clone, Copy, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum FilterErrorPolicy {
+    /// Carry on as if the filter had not matched the torrent
+    FailOpen,
+    /// Keep the torrent
+    FailClosed,
+    /// Abort the whole cleanup run without removing anything
+    Abort,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Default, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum CleanupAction {
+    /// Remove the torrent together with its files
+    #[default]
+    Delete,
+    /// Remove the torrent but keep its files on disk
+    Remove,
+    /// Never remove the torrent
+    Ignore,
+}
+
+/// Cleanup rules for a qBittorrent category.
+///
+/// When `ratio` or `seeding_time` is set they take precedence over the
+/// thresholds of any tracker the torrent belongs to (most specific rule
+/// first). The same applies to `inactive_for` and `size_rule`. Tracker
+/// protections such as `ignore` still apply.
 #[derive(Clone, Deserialize, Debug)]
 pub struct CategoriesConfig {
     pub name: String,
     #[serde(default)]
     pub ignore: bool,
+    pub ratio: Option<f64>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub seeding_time: Option<Duration>,
+    #[serde(default)]
+    pub require_both: bool,
+    pub action: Option<CleanupAction>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub inactive_for: Option<Duration>,
+    pub size_rule: Option<SizeRuleConfig>,
+}
+
+impl CategoriesConfig {
+    pub fn action(&self) -> CleanupAction {
+        if self.ignore {
+            CleanupAction::Ignore
+        } else {
+            self.action.clone().unwrap_or_default()
+        }
+    }
+
+    pub fn has_thresholds(&self) -> bool {
+        self.ratio.is_some() || self.seeding_time.is_some()
+    }
+
+    pub fn has_expiry_rules(&self) -> bool {
+        self.inactive_for.is_some() || self.size_rule.is_some()
+    }
+}
+
+/// Cleanup rules for a qBittorrent tag.
+///
+/// Torrents with an `ignore` tag are never removed. When any tag sets
+/// `opt_in`, only torrents carrying one of those tags are cleaned up. Tag
+/// thresholds take precedence over category and tracker thresholds, and a
+/// torrent with several such tags must reach all of them.
+#[derive(Clone, Deserialize, Debug)]
+pub struct TagConfig {
+    pub name: String,
+    #[serde(default)]
+    pub ignore: bool,
+    #[serde(default)]
+    pub opt_in: bool,
+    pub ratio: Option<f64>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub seeding_time: Option<Duration>,
+    #[serde(default)]
+    pub require_both: bool,
+}
+
+impl TagConfig {
+    pub fn has_thresholds(&self) -> bool {
+        self.ratio.is_some() || self.seeding_time.is_some()
+    }
+}
+
+fn default_usenet_cleanup_age() -> Duration {
+    Duration::from_secs(24 * 3600)
+}
+
+/// Deletes the jobs that SABnzbd and NZBGet finished, together with their
+/// folders below the `download_root` of each client, once no *arr tracks
+/// them anymore.
+#[derive(Clone, Deserialize, Debug)]
+pub struct UsenetCleanupConfig {
+    /// How long a job stays in the history after it finished
+    #[serde(with = "humantime_serde", default = "default_usenet_cleanup_age")]
+    pub older_than: Duration,
+    /// Only jobs of these categories, which should be those the *arrs send
+    /// their downloads with, so manual downloads are never touched
+    pub categories: Vec<String>,
 }
 
 fn default_cleanup_schedule() -> Schedule {
-    Schedule("*/5 * * * *".to_owned())
+    "*/5 * * * *".parse().unwrap()
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -140,30 +363,263 @@ pub struct CleanupConfig {
     pub ratio: Option<f64>,
     pub trackers: Option<Vec<TrackerConfig>>,
     pub categories: Option<Vec<CategoriesConfig>>,
+    pub tags: Option<Vec<TagConfig>>,
+    /// Torrents are only removed when this expression holds for them
+    pub condition: Option<Expression>,
+    pub usenet: Option<UsenetCleanupConfig>,
+    /// Tightens the error policy declared by each filter, which is kept when
+    /// it is already stricter
+    pub on_filter_error: Option<FilterErrorPolicy>,
+    /// Longest a single run may take before it is cancelled
+    #[serde(with = "humantime_serde::option", default)]
+    pub timeout: Option<Duration>,
     pub dry_run: Option<bool>,
+    /// Run the task as soon as arrmate starts instead of waiting for the schedule
+    #[serde(default)]
+    pub run_on_start: bool,
+}
+
+fn default_slow_checks() -> usize {
+    3
+}
+
+/// Minimum speed for releases larger than `larger_than`, replacing the
+/// default threshold of the slow download rule.
+#[derive(Clone, Deserialize, Debug)]
+pub struct LargeReleaseConfig {
+    pub larger_than: ByteSize,
+    pub min_speed: ByteSize,
+}
+
+/// Removes and blocklists downloads slower than `min_speed` (per second) for
+/// `checks` consecutive runs, e.g. `min_speed: 100KB`.
+#[derive(Clone, Deserialize, Debug)]
+pub struct SlowDownloadConfig {
+    pub min_speed: ByteSize,
+    #[serde(default = "default_slow_checks")]
+    pub checks: usize,
+    pub large: Option<LargeReleaseConfig>,
+}
+
+/// Removes queue items that stay in a state longer than its limit, counted
+/// from when the item was added. States without a limit are left alone.
+#[derive(Clone, Deserialize, Debug)]
+pub struct StuckConfig {
+    /// Magnet links still waiting for metadata
+    #[serde(with = "humantime_serde::option", default)]
+    pub metadata: Option<Duration>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub queued: Option<Duration>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub delay: Option<Duration>,
+    #[serde(with = "humantime_serde::option", default)]
+    pub download_client_unavailable: Option<Duration>,
+    /// Also blocklist the release so it is not grabbed again
+    #[serde(default)]
+    pub blocklist: bool,
+}
+
+/// What removals are counted against for `max_retries`. Radarr items are
+/// always counted per movie.
+#[derive(Clone, Copy, Deserialize, PartialEq, Default, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum RetryScope {
+    #[default]
+    Episode,
+    Series,
+}
+
+fn default_max_retries_window() -> Duration {
+    Duration::from_secs(7 * 24 * 60 * 60)
+}
+
+/// Stops retrying an episode, series or movie once its releases were removed
+/// `count` times within `window`.
+#[derive(Clone, Deserialize, Debug)]
+pub struct MaxRetriesConfig {
+    pub count: usize,
+    #[serde(with = "humantime_serde", default = "default_max_retries_window")]
+    pub window: Duration,
+    #[serde(default)]
+    pub per: RetryScope,
+    /// Also unmonitor the item so the *arr stops grabbing releases for it
+    #[serde(default)]
+    pub unmonitor: bool,
+}
+
+fn default_manual_import() -> bool {
+    true
+}
+
+/// Handles finished downloads that the *arr does not import on its own.
+#[derive(Clone, Deserialize, Debug)]
+pub struct ImportBlockedConfig {
+    /// Import the download through the manual import once, when it holds a
+    /// single file matching the episode or movie of the queue item
+    #[serde(default = "default_manual_import")]
+    pub manual_import: bool,
+    /// Remove and blocklist the download once it has been blocked this long
+    #[serde(with = "humantime_serde::option", default)]
+    pub timeout: Option<Duration>,
+}
+
+fn default_usenet_failures() -> Vec<UsenetFailure> {
+    vec![
+        UsenetFailure::MissingArticles,
+        UsenetFailure::RepairFailed,
+        UsenetFailure::Encrypted,
+        UsenetFailure::UnpackFailed,
+    ]
+}
+
+/// Removes and blocklists releases that SABnzbd or NZBGet failed, or are
+/// going to fail, to download.
+#[derive(Clone, Deserialize, Debug)]
+pub struct UsenetRetryConfig {
+    /// Failures that remove the release, all of them but `other` by default
+    #[serde(default = "default_usenet_failures")]
+    pub failures: Vec<UsenetFailure>,
+    /// Delete failed jobs that no *arr tracks anymore from the history
+    #[serde(default)]
+    pub clean_failed: bool,
+}
+
+fn default_denied_extensions() -> Vec<String> {
+    [
+        "exe", "lnk", "bat", "cmd", "com", "scr", "pif", "msi", "vbs", "js", "jar", "ps1",
+    ]
+    .map(str::to_owned)
+    .to_vec()
+}
+
+fn default_require_video() -> bool {
+    true
+}
+
+fn default_video_extensions() -> Vec<String> {
+    [
+        "mkv", "mp4", "m4v", "avi", "mov", "wmv", "mpg", "mpeg", "ts", "m2ts", "webm",
+    ]
+    .map(str::to_owned)
+    .to_vec()
+}
+
+/// Removes and blocklists torrents whose files give away a fake or
+/// password-protected release, as soon as qBittorrent has their metadata.
+#[derive(Clone, Deserialize, Debug)]
+pub struct ContentsConfig {
+    /// Extensions, without the dot, of files no real release contains
+    #[serde(default = "default_denied_extensions")]
+    pub denied_extensions: Vec<String>,
+    /// Remove torrents without any video file, such as a lone archive
+    #[serde(default = "default_require_video")]
+    pub require_video: bool,
+    #[serde(default = "default_video_extensions")]
+    pub video_extensions: Vec<String>,
 }
 
 fn default_retry_schedule() -> Schedule {
-    Schedule("*/5 * * * *".to_owned())
+    "*/5 * * * *".parse().unwrap()
 }
 
 #[derive(Clone, Deserialize, Debug)]
 pub struct RetryConfig {
     #[serde(default = "default_retry_schedule")]
     pub schedule: Schedule,
+    /// Longest a single run may take before it is cancelled
     #[serde(with = "humantime_serde::option", default)]
-    #[allow(unused)]
     pub timeout: Option<Duration>,
     pub dry_run: Option<bool>,
+    /// Run the task as soon as arrmate starts instead of waiting for the schedule
+    #[serde(default)]
+    pub run_on_start: bool,
+    pub slow: Option<SlowDownloadConfig>,
+    pub stuck: Option<StuckConfig>,
+    /// Search for the episodes or movies of removed items instead of relying
+    /// on the *arr to grab a replacement on its own
+    #[serde(default)]
+    pub search: bool,
+    pub max_retries: Option<MaxRetriesConfig>,
+    pub import_blocked: Option<ImportBlockedConfig>,
+    pub usenet: Option<UsenetRetryConfig>,
+    pub contents: Option<ContentsConfig>,
+}
+
+fn default_server_address() -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], 8686))
+}
+
+#[derive(Clone, Deserialize, PartialEq, Debug)]
+pub struct ServerConfig {
+    #[serde(default = "default_server_address")]
+    pub address: SocketAddr,
+    /// Bearer token required by the API requests that start or change
+    /// anything, such as running a task
+    pub token: Option<String>,
+}
+
+fn default_notification_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+/// Runs `command` for every notification, with the title and message in the
+/// `ARRMATE_TITLE` and `ARRMATE_MESSAGE` environment variables.
+#[derive(Clone, Deserialize, PartialEq, Debug)]
+pub struct NotificationConfig {
+    pub command: Vec<String>,
+    #[serde(with = "humantime_serde", default = "default_notification_timeout")]
+    pub timeout: Duration,
+}
+
+fn default_history_enabled() -> bool {
+    true
+}
+
+fn default_history_path() -> PathBuf {
+    PathBuf::from("arrmate.db")
+}
+
+fn default_history_retention() -> Duration {
+    Duration::from_secs(90 * 24 * 60 * 60)
+}
+
+#[derive(Clone, Deserialize, PartialEq, Debug)]
+pub struct HistoryConfig {
+    #[serde(default = "default_history_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_history_path")]
+    pub path: PathBuf,
+    /// How long runs are kept before they are pruned from the database
+    #[serde(with = "humantime_serde", default = "default_history_retention")]
+    pub retention: Duration,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: default_history_path(),
+            retention: default_history_retention(),
+        }
+    }
 }
 
 #[derive(Clone, Deserialize, Debug)]
 pub struct ConfigData {
+    pub server: Option<ServerConfig>,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    /// How long running tasks may take to finish when arrmate shuts down
+    #[serde(with = "humantime_serde::option", default)]
+    pub shutdown_grace_period: Option<Duration>,
+    pub notifications: Option<NotificationConfig>,
     pub cleanup: Option<CleanupConfig>,
     pub retry: Option<RetryConfig>,
     pub qbittorrent: Option<QBittorrentConfig>,
     pub sonarr: Option<SonarrConfig>,
     pub radarr: Option<RadarrConfig>,
+    pub sabnzbd: Option<SabnzbdConfig>,
+    pub nzbget: Option<NzbgetConfig>,
 }
 
 #[cfg(test)]
@@ -194,6 +650,22 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn tracker_without_domain_or_url_pattern_errors() {
+        let result: Result<TrackerConfig, _> = serde_yaml::from_str("name: t\nratio: 2");
+        assert!(result.is_err());
+        let result: Result<TrackerConfig, _> = serde_yaml::from_str("name: t\ndomains: a.com");
+        assert!(result.is_err());
+        let t = parse_tracker("name: t\nurl_pattern: 'https://a\\.com/.*'");
+        assert!(t.domain.is_empty());
+    }
+
+    #[test]
+    fn tracker_with_unknown_key_parses() {
+        let t = parse_tracker("name: t\ndomain: a.com\nnotes: keep seeding");
+        assert_eq!(t.domain, vec!["a.com".to_string()]);
+    }
+
     // ── TrackerConfig serde defaults ─────────────────────────────────────────
 
     #[test]
@@ -220,6 +692,256 @@ mod tests {
         assert_eq!(t.seeding_time, Some(Duration::from_secs(7200)));
     }
 
+    #[test]
+    fn tracker_expiry_rules_parse() {
+        let t = parse_tracker(
+            "name: t\ndomain: d\ninactive_for: 30d\nsize_rule:\n  larger_than: 50GB\n  ratio_below: 0.5\n  seeding_time: 7d",
+        );
+        assert_eq!(t.inactive_for, Some(Duration::from_secs(30 * 24 * 3600)));
+        let rule = t.size_rule.expect("size rule present");
+        assert_eq!(rule.larger_than, ByteSize(50_000_000_000));
+        assert_eq!(rule.ratio_below, Some(0.5));
+        assert_eq!(rule.seeding_time, Some(Duration::from_secs(7 * 24 * 3600)));
+    }
+
+    #[test]
+    fn tracker_seeding_limits_parse() {
+        let t = parse_tracker(
+            "name: t\ndomain: d\nmax_torrents: 100\nmax_total_size: 2TB\nevict: oldest",
+        );
+        assert_eq!(t.max_torrents, Some(100));
+        assert_eq!(t.max_total_size, Some(ByteSize(2_000_000_000_000)));
+        assert_eq!(t.evict, Some(EvictionOrder::Oldest));
+    }
+
+    // ── TrackerConfig::matches ───────────────────────────────────────────────
+
+    fn tracker_matches(yaml: &str, url: &str) -> bool {
+        parse_tracker(yaml).matches(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn tracker_matches_exact_domain() {
+        assert!(tracker_matches(
+            "name: t\ndomain: example.org",
+            "https://example.org/announce"
+        ));
+    }
+
+    #[test]
+    fn tracker_matches_subdomain() {
+        assert!(tracker_matches(
+            "name: t\ndomain: example.org",
+            "https://tracker.example.org/announce"
+        ));
+        assert!(!tracker_matches(
+            "name: t\ndomain: example.org",
+            "https://notexample.org/announce"
+        ));
+    }
+
+    #[test]
+    fn tracker_matches_wildcard() {
+        let yaml = "name: t\ndomain: tracker*.example.org";
+        assert!(tracker_matches(
+            yaml,
+            "https://tracker2.example.org/announce"
+        ));
+        assert!(!tracker_matches(yaml, "https://www.example.org/announce"));
+    }
+
+    #[test]
+    fn tracker_matches_ip_host() {
+        assert!(tracker_matches(
+            "name: t\ndomain: 10.0.0.5",
+            "udp://10.0.0.5:6969/announce"
+        ));
+    }
+
+    #[test]
+    fn tracker_matches_url_pattern() {
+        let yaml = "name: t\nurl_pattern: '^udp://[^/]+:1337/'";
+        assert!(tracker_matches(yaml, "udp://1.2.3.4:1337/announce"));
+        assert!(!tracker_matches(yaml, "udp://1.2.3.4:6969/announce"));
+    }
+
+    #[test]
+    fn tracker_invalid_url_pattern_errors() {
+        let result: Result<TrackerConfig, _> = serde_yaml::from_str("name: t\nurl_pattern: '('");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn qbittorrent_max_concurrent_requests_defaults() {
+        let config: QBittorrentConfig =
+            serde_yaml::from_str("username: u\npassword: p\nhost: http://localhost:8080").unwrap();
+        assert_eq!(config.max_concurrent_requests, 8);
+    }
+
+    #[test]
+    fn server_address_defaults_to_localhost() {
+        let config: ServerConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.address, "127.0.0.1:8686".parse().unwrap());
+    }
+
+    #[test]
+    fn cleanup_timeout_parse() {
+        let cleanup: CleanupConfig = serde_yaml::from_str("timeout: 10m").unwrap();
+        assert_eq!(cleanup.timeout, Some(Duration::from_secs(600)));
+        let cleanup: CleanupConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(cleanup.timeout, None);
+    }
+
+    #[test]
+    fn run_on_start_defaults_to_false() {
+        let cleanup: CleanupConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(!cleanup.run_on_start);
+        let retry: RetryConfig = serde_yaml::from_str("run_on_start: true").unwrap();
+        assert!(retry.run_on_start);
+    }
+
+    #[test]
+    fn stuck_parse() {
+        let retry: RetryConfig =
+            serde_yaml::from_str("stuck:\n  metadata: 30m\n  delay: 2d\n  blocklist: true\n")
+                .unwrap();
+        let stuck = retry.stuck.unwrap();
+        assert_eq!(stuck.metadata, Some(Duration::from_secs(30 * 60)));
+        assert_eq!(stuck.queued, None);
+        assert_eq!(stuck.delay, Some(Duration::from_secs(2 * 24 * 60 * 60)));
+        assert_eq!(stuck.download_client_unavailable, None);
+        assert!(stuck.blocklist);
+    }
+
+    #[test]
+    fn max_retries_parse() {
+        let retry: RetryConfig =
+            serde_yaml::from_str("max_retries:\n  count: 3\n  per: series\n  unmonitor: true\n")
+                .unwrap();
+        let max_retries = retry.max_retries.unwrap();
+        assert_eq!(max_retries.count, 3);
+        assert_eq!(max_retries.window, Duration::from_secs(7 * 24 * 60 * 60));
+        assert_eq!(max_retries.per, RetryScope::Series);
+        assert!(max_retries.unmonitor);
+    }
+
+    #[test]
+    fn notifications_parse() {
+        let config: ConfigData =
+            serde_yaml::from_str("notifications:\n  command: [/config/notify.sh, retry]\n")
+                .unwrap();
+        let notifications = config.notifications.unwrap();
+        assert_eq!(notifications.command, vec!["/config/notify.sh", "retry"]);
+        assert_eq!(notifications.timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn usenet_parse() {
+        let retry: RetryConfig = serde_yaml::from_str("usenet: {}\n").unwrap();
+        let usenet = retry.usenet.unwrap();
+        assert!(!usenet.failures.contains(&UsenetFailure::Other));
+        assert!(!usenet.clean_failed);
+
+        let retry: RetryConfig =
+            serde_yaml::from_str("usenet:\n  failures: [encrypted]\n  clean_failed: true\n")
+                .unwrap();
+        let usenet = retry.usenet.unwrap();
+        assert_eq!(usenet.failures, vec![UsenetFailure::Encrypted]);
+        assert!(usenet.clean_failed);
+
+        let cleanup: UsenetCleanupConfig =
+            serde_yaml::from_str("categories: [tv, movies]\n").unwrap();
+        assert_eq!(cleanup.older_than, Duration::from_secs(24 * 3600));
+        assert_eq!(
+            cleanup.categories,
+            vec!["tv".to_owned(), "movies".to_owned()]
+        );
+        assert!(serde_yaml::from_str::<UsenetCleanupConfig>("older_than: 1d\n").is_err());
+    }
+
+    #[test]
+    fn contents_parse() {
+        let retry: RetryConfig = serde_yaml::from_str("contents: {}\n").unwrap();
+        let contents = retry.contents.unwrap();
+        assert!(contents.denied_extensions.contains(&"exe".to_owned()));
+        assert!(contents.require_video);
+        assert!(contents.video_extensions.contains(&"mkv".to_owned()));
+
+        let retry: RetryConfig =
+            serde_yaml::from_str("contents:\n  denied_extensions: [iso]\n  require_video: false\n")
+                .unwrap();
+        let contents = retry.contents.unwrap();
+        assert_eq!(contents.denied_extensions, vec!["iso".to_owned()]);
+        assert!(!contents.require_video);
+    }
+
+    #[test]
+    fn import_blocked_parse() {
+        let retry: RetryConfig = serde_yaml::from_str("import_blocked:\n  timeout: 12h\n").unwrap();
+        let import_blocked = retry.import_blocked.unwrap();
+        assert!(import_blocked.manual_import);
+        assert_eq!(import_blocked.timeout, Some(Duration::from_secs(12 * 3600)));
+    }
+
+    #[test]
+    fn slow_download_parse() {
+        let retry: RetryConfig = serde_yaml::from_str(
+            "slow:\n  min_speed: 100KB\n  large:\n    larger_than: 20GB\n    min_speed: 1MB\n",
+        )
+        .unwrap();
+        let slow = retry.slow.unwrap();
+        assert_eq!(slow.min_speed, ByteSize(100_000));
+        assert_eq!(slow.checks, 3);
+        let large = slow.large.unwrap();
+        assert_eq!(large.larger_than, ByteSize(20_000_000_000));
+        assert_eq!(large.min_speed, ByteSize(1_000_000));
+    }
+
+    #[test]
+    fn history_is_enabled_by_default() {
+        let config: ConfigData = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.history, HistoryConfig::default());
+        assert!(config.history.enabled);
+        assert_eq!(config.history.path, PathBuf::from("arrmate.db"));
+        assert_eq!(
+            config.history.retention,
+            Duration::from_secs(90 * 24 * 3600)
+        );
+
+        let config: ConfigData = serde_yaml::from_str("history:\n  retention: 30d\n").unwrap();
+        assert_eq!(
+            config.history.retention,
+            Duration::from_secs(30 * 24 * 3600)
+        );
+    }
+
+    #[test]
+    fn filter_error_policy_parse() {
+        for (value, expected) in [
+            ("fail_open", FilterErrorPolicy::FailOpen),
+            ("fail_closed", FilterErrorPolicy::FailClosed),
+            ("abort", FilterErrorPolicy::Abort),
+        ] {
+            let policy: FilterErrorPolicy = serde_yaml::from_str(value).unwrap();
+            assert_eq!(policy, expected);
+        }
+    }
+
+    // ── ByteSize deserialization ─────────────────────────────────────────────
+
+    #[test]
+    fn byte_size_parses_numbers_and_units() {
+        assert_eq!(
+            serde_yaml::from_str::<ByteSize>("1024").unwrap(),
+            ByteSize(1024)
+        );
+        assert_eq!(
+            serde_yaml::from_str::<ByteSize>("2GiB").unwrap(),
+            ByteSize(2 * 1024 * 1024 * 1024)
+        );
+        assert!(serde_yaml::from_str::<ByteSize>("lots").is_err());
+    }
+
     // ── TrackerIgnore deserialization ────────────────────────────────────────
 
     fn parse_ignore(value: &str) -> TrackerIgnore {
@@ -264,54 +986,82 @@ mod tests {
         assert!(c.ignore);
     }
 
-    // ── CleanupConfig serde defaults ─────────────────────────────────────
+    #[test]
+    fn categories_thresholds_parse() {
+        let c: CategoriesConfig = serde_yaml::from_str(
+            "name: tv-sonarr\nratio: 1.5\nseeding_time: 3d\nrequire_both: true",
+        )
+        .unwrap();
+        assert_eq!(c.ratio, Some(1.5));
+        assert_eq!(c.seeding_time, Some(Duration::from_secs(3 * 24 * 3600)));
+        assert!(c.require_both);
+        assert!(c.has_thresholds());
+    }
 
     #[test]
-    fn cleanup_config_defaults() {
-        let c: CleanupConfig = serde_yaml::from_str("").unwrap();
-        assert_eq!(c.schedule, Schedule("*/5 * * * *".to_owned()));
+    fn categories_action_defaults_to_delete() {
+        let c: CategoriesConfig = serde_yaml::from_str("name: movies").unwrap();
+        assert_eq!(c.action(), CleanupAction::Delete);
+        assert!(!c.has_thresholds());
     }
 
-    // ── RetryConfig serde defaults ─────────────────────────────────────
+    #[test]
+    fn categories_action_parses() {
+        let c: CategoriesConfig = serde_yaml::from_str("name: movies\naction: remove").unwrap();
+        assert_eq!(c.action(), CleanupAction::Remove);
+    }
 
     #[test]
-    fn retry_config_defaults() {
-        let c: RetryConfig = serde_yaml::from_str("").unwrap();
-        assert_eq!(c.schedule, Schedule("*/5 * * * *".to_owned()));
+    fn categories_ignore_overrides_action() {
+        let c: CategoriesConfig =
+            serde_yaml::from_str("name: movies\nignore: true\naction: delete").unwrap();
+        assert_eq!(c.action(), CleanupAction::Ignore);
     }
 
-    // ── Schedule deserialization ──────────────────────────────────────────────
+    // ── TagConfig serde defaults ─────────────────────────────────────────
 
-    fn parse_schedule(s: &str) -> Result<Schedule, serde_yaml::Error> {
-        serde_yaml::from_str(&format!("'{s}'"))
+    #[test]
+    fn tag_config_defaults() {
+        let t: TagConfig = serde_yaml::from_str("name: keep").unwrap();
+        assert!(!t.ignore);
+        assert!(!t.opt_in);
+        assert!(!t.has_thresholds());
     }
 
     #[test]
-    fn schedule_parses_cron_expression() {
-        let s = parse_schedule("0 */5 * * * * *").unwrap();
-        assert!(matches!(s, Schedule(_)));
+    fn tag_config_parses_thresholds() {
+        let t: TagConfig =
+            serde_yaml::from_str("name: cross-seed\nratio: 3\nseeding_time: 30d").unwrap();
+        assert_eq!(t.ratio, Some(3.0));
+        assert!(t.has_thresholds());
     }
 
+    // ── CleanupConfig serde defaults ─────────────────────────────────────
+
     #[test]
-    fn schedule_invalid_errors() {
-        assert!(parse_schedule("not-a-schedule").is_err());
+    fn cleanup_config_defaults() {
+        let c: CleanupConfig = serde_yaml::from_str("").unwrap();
+        assert_eq!(c.schedule, "*/5 * * * *".parse().unwrap());
     }
 
     #[test]
-    fn schedule_next_date_is_in_the_future() {
-        let s = parse_schedule("0 */5 * * * * *").unwrap();
-        let now = time::OffsetDateTime::now_utc();
-        let next = s.next_date(now).expect("should have a next occurrence");
-        assert!(next > now);
+    fn cleanup_config_condition_parses() {
+        let c: CleanupConfig =
+            serde_yaml::from_str("condition: 'ratio >= 2 or seeding_time > 14d'").unwrap();
+        assert!(c.condition.is_some());
     }
 
     #[test]
-    fn schedule_next_date_respects_cron_minute_alignment() {
-        // "every minute at second 0" — next occurrence should be within 60 seconds
-        let s = parse_schedule("0 * * * * * *").unwrap();
-        let now = time::OffsetDateTime::now_utc();
-        let next = s.next_date(now).expect("should have a next occurrence");
-        let delta = next - time::OffsetDateTime::now_utc();
-        assert!(delta <= time::Duration::seconds(60));
+    fn cleanup_config_invalid_condition_errors() {
+        let result: Result<CleanupConfig, _> = serde_yaml::from_str("condition: 'ratio >= '");
+        assert!(result.is_err());
+    }
+
+    // ── RetryConfig serde defaults ─────────────────────────────────────
+
+    #[test]
+    fn retry_config_defaults() {
+        let c: RetryConfig = serde_yaml::from_str("").unwrap();
+        assert_eq!(c.schedule, "*/5 * * * *".parse().unwrap());
     }
 }
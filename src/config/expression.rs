@@ -0,0 +1,587 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum ExpressionError {
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+
+    #[error("unexpected token '{0}'")]
+    UnexpectedToken(String),
+
+    #[error("unterminated string literal")]
+    UnterminatedString,
+
+    #[error("unknown field '{0}'")]
+    UnknownField(String),
+
+    #[error("operator '{operator}' is not supported for field '{field}'")]
+    UnsupportedOperator { field: String, operator: String },
+
+    #[error("invalid value '{value}' for field '{field}'")]
+    InvalidValue { field: String, value: String },
+}
+
+/// Torrent attributes that can be referenced from an expression.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Field {
+    Size,
+    Ratio,
+    SeedingTime,
+    Category,
+    Tags,
+    LastActivity,
+    AddedOn,
+    Tracker,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FieldKind {
+    Number,
+    Size,
+    Duration,
+    Text,
+    List,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "size" => Some(Field::Size),
+            "ratio" => Some(Field::Ratio),
+            "seeding_time" => Some(Field::SeedingTime),
+            "category" => Some(Field::Category),
+            "tags" | "tag" => Some(Field::Tags),
+            "last_activity" => Some(Field::LastActivity),
+            "added_on" => Some(Field::AddedOn),
+            "tracker" => Some(Field::Tracker),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Field::Size => "size",
+            Field::Ratio => "ratio",
+            Field::SeedingTime => "seeding_time",
+            Field::Category => "category",
+            Field::Tags => "tags",
+            Field::LastActivity => "last_activity",
+            Field::AddedOn => "added_on",
+            Field::Tracker => "tracker",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            Field::Size => FieldKind::Size,
+            Field::Ratio => FieldKind::Number,
+            Field::SeedingTime | Field::LastActivity | Field::AddedOn => FieldKind::Duration,
+            Field::Category => FieldKind::Text,
+            Field::Tags | Field::Tracker => FieldKind::List,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operator::Eq => "==",
+            Operator::Ne => "!=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::Contains => "contains",
+        })
+    }
+}
+
+/// Value of a [`Field`] for the torrent being evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Duration(Duration),
+    Text(String),
+    List(Vec<String>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Literal {
+    Number(f64),
+    Duration(Duration),
+    Text(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Or(Box<Node>, Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+    Compare {
+        field: Field,
+        operator: Operator,
+        literal: Literal,
+    },
+}
+
+/// A boolean condition over torrent fields, e.g.
+/// `ratio >= 2 or (seeding_time > 14d and tracker == "example.org")`.
+///
+/// Comparisons are `field operator value` joined with `and`, `or`, `not` and
+/// parentheses. `size` accepts byte units (`50GB`, `1.5GiB`), `seeding_time`,
+/// `last_activity` and `added_on` accept durations (`14d`, `2h30m`) where the
+/// last two are measured as time elapsed since that moment. `tags` and
+/// `tracker` match when any of their values compares true.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl Expression {
+    /// Evaluates the expression, looking up field values through `resolve`.
+    /// Comparisons against a field without a value are false.
+    pub fn evaluate(&self, resolve: &dyn Fn(Field) -> Option<Value>) -> bool {
+        Self::evaluate_node(&self.root, resolve)
+    }
+
+    fn evaluate_node(node: &Node, resolve: &dyn Fn(Field) -> Option<Value>) -> bool {
+        match node {
+            Node::Or(lhs, rhs) => {
+                Self::evaluate_node(lhs, resolve) || Self::evaluate_node(rhs, resolve)
+            }
+            Node::And(lhs, rhs) => {
+                Self::evaluate_node(lhs, resolve) && Self::evaluate_node(rhs, resolve)
+            }
+            Node::Not(inner) => !Self::evaluate_node(inner, resolve),
+            Node::Compare {
+                field,
+                operator,
+                literal,
+            } => resolve(*field).is_some_and(|value| compare(&value, *operator, literal)),
+        }
+    }
+}
+
+fn compare_ordered<T: PartialOrd>(lhs: &T, operator: Operator, rhs: &T) -> bool {
+    match operator {
+        Operator::Eq => lhs == rhs,
+        Operator::Ne => lhs != rhs,
+        Operator::Lt => lhs < rhs,
+        Operator::Le => lhs <= rhs,
+        Operator::Gt => lhs > rhs,
+        Operator::Ge => lhs >= rhs,
+        Operator::Contains => false,
+    }
+}
+
+fn compare_text(lhs: &str, operator: Operator, rhs: &str) -> bool {
+    match operator {
+        Operator::Eq => lhs == rhs,
+        Operator::Ne => lhs != rhs,
+        Operator::Contains => lhs.contains(rhs),
+        _ => false,
+    }
+}
+
+fn compare(value: &Value, operator: Operator, literal: &Literal) -> bool {
+    match (value, literal) {
+        (Value::Number(lhs), Literal::Number(rhs)) => compare_ordered(lhs, operator, rhs),
+        (Value::Duration(lhs), Literal::Duration(rhs)) => compare_ordered(lhs, operator, rhs),
+        (Value::Text(lhs), Literal::Text(rhs)) => compare_text(lhs, operator, rhs),
+        (Value::List(values), Literal::Text(rhs)) => match operator {
+            Operator::Ne => values.iter().all(|lhs| lhs != rhs),
+            _ => values.iter().any(|lhs| compare_text(lhs, operator, rhs)),
+        },
+        _ => false,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    Word(String),
+    Text(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LParen => f.write_str("("),
+            Token::RParen => f.write_str(")"),
+            Token::Operator(operator) => operator.fmt(f),
+            Token::And => f.write_str("and"),
+            Token::Or => f.write_str("or"),
+            Token::Not => f.write_str("not"),
+            Token::Word(word) => f.write_str(word),
+            Token::Text(text) => write!(f, "\"{text}\""),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let followed_by_eq = chars.next_if_eq(&'=').is_some();
+                let operator = match (c, followed_by_eq) {
+                    ('=', true) => Operator::Eq,
+                    ('!', true) => Operator::Ne,
+                    ('<', true) => Operator::Le,
+                    ('>', true) => Operator::Ge,
+                    ('<', false) => Operator::Lt,
+                    ('>', false) => Operator::Gt,
+                    _ => return Err(ExpressionError::UnexpectedToken(c.to_string())),
+                };
+                tokens.push(Token::Operator(operator));
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => text.push(next),
+                        None => return Err(ExpressionError::UnterminatedString),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut word = String::new();
+                while let Some(next) =
+                    chars.next_if(|n| n.is_alphanumeric() || matches!(n, '_' | '.' | '-'))
+                {
+                    word.push(next);
+                }
+                tokens.push(match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "contains" => Token::Operator(Operator::Contains),
+                    _ => Token::Word(word),
+                });
+            }
+            c => return Err(ExpressionError::UnexpectedToken(c.to_string())),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parses sizes such as `500`, `50GB` or `1.5GiB` into bytes.
+pub(super) fn parse_size(s: &str) -> Option<f64> {
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let multiplier = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "k" | "kb" => 1e3,
+        "m" | "mb" => 1e6,
+        "g" | "gb" => 1e9,
+        "t" | "tb" => 1e12,
+        "kib" => 1024.0,
+        "mib" => 1024.0 * 1024.0,
+        "gib" => 1024.0 * 1024.0 * 1024.0,
+        "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    number.parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(ExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn parse_or(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            node = Node::Or(Box::new(node), Box::new(self.parse_and()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.parse_not()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            node = Node::And(Box::new(node), Box::new(self.parse_not()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, ExpressionError> {
+        if self.peek() == Some(&Token::Not) {
+            self.position += 1;
+            return Ok(Node::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, ExpressionError> {
+        match self.next()? {
+            Token::LParen => {
+                let node = self.parse_or()?;
+                match self.next()? {
+                    Token::RParen => Ok(node),
+                    token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+                }
+            }
+            Token::Word(name) => {
+                let field = Field::from_name(&name).ok_or(ExpressionError::UnknownField(name))?;
+                let operator = match self.next()? {
+                    Token::Operator(operator) => operator,
+                    token => return Err(ExpressionError::UnexpectedToken(token.to_string())),
+                };
+                let literal = self.parse_literal(field, operator)?;
+                Ok(Node::Compare {
+                    field,
+                    operator,
+                    literal,
+                })
+            }
+            token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    fn parse_literal(
+        &mut self,
+        field: Field,
+        operator: Operator,
+    ) -> Result<Literal, ExpressionError> {
+        let kind = field.kind();
+        let text_operator = matches!(operator, Operator::Eq | Operator::Ne | Operator::Contains);
+        let supported = match kind {
+            FieldKind::Text | FieldKind::List => text_operator,
+            FieldKind::Number | FieldKind::Size | FieldKind::Duration => {
+                operator != Operator::Contains
+            }
+        };
+        if !supported {
+            return Err(ExpressionError::UnsupportedOperator {
+                field: field.name().to_owned(),
+                operator: operator.to_string(),
+            });
+        }
+
+        let token = self.next()?;
+        let raw = match &token {
+            Token::Word(word) => word.clone(),
+            Token::Text(text) if matches!(kind, FieldKind::Text | FieldKind::List) => text.clone(),
+            token => return Err(ExpressionError::UnexpectedToken(token.to_string())),
+        };
+        let invalid = || ExpressionError::InvalidValue {
+            field: field.name().to_owned(),
+            value: raw.clone(),
+        };
+
+        match kind {
+            FieldKind::Number => raw.parse().map(Literal::Number).map_err(|_| invalid()),
+            FieldKind::Size => parse_size(&raw).map(Literal::Number).ok_or_else(invalid),
+            FieldKind::Duration => humantime::parse_duration(&raw)
+                .map(Literal::Duration)
+                .map_err(|_| invalid()),
+            FieldKind::Text | FieldKind::List => Ok(Literal::Text(raw.clone())),
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(ExpressionError::UnexpectedToken(token.to_string()));
+        }
+        Ok(Expression {
+            source: s.to_owned(),
+            root,
+        })
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).and_then(|s| {
+            Expression::from_str(&s)
+                .map_err(|e| serde::de::Error::custom(format!("invalid expression: {e}")))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(field: Field) -> Option<Value> {
+        match field {
+            Field::Size => Some(Value::Number(60e9)),
+            Field::Ratio => Some(Value::Number(1.5)),
+            Field::SeedingTime => Some(Value::Duration(Duration::from_secs(20 * 86400))),
+            Field::Category => Some(Value::Text("tv-sonarr".to_owned())),
+            Field::Tags => Some(Value::List(vec!["keep".to_owned(), "manual".to_owned()])),
+            Field::LastActivity => None,
+            Field::AddedOn => Some(Value::Duration(Duration::from_secs(30 * 86400))),
+            Field::Tracker => Some(Value::List(vec!["tracker.example.org".to_owned()])),
+        }
+    }
+
+    fn eval(s: &str) -> bool {
+        s.parse::<Expression>().unwrap().evaluate(&values)
+    }
+
+    #[test]
+    fn parses_and_evaluates_comparisons() {
+        assert!(eval("ratio >= 1.5"));
+        assert!(!eval("ratio > 1.5"));
+        assert!(eval("size > 50GB"));
+        assert!(!eval("size < 1.5GiB"));
+        assert!(eval("seeding_time > 14d"));
+        assert!(eval("added_on >= 4w"));
+    }
+
+    #[test]
+    fn respects_precedence_and_parentheses() {
+        assert!(eval(
+            "ratio >= 2 or seeding_time > 14d and category == \"tv-sonarr\""
+        ));
+        assert!(!eval(
+            "(ratio >= 2 or seeding_time > 14d) and category == 'movies'"
+        ));
+        assert!(eval("not ratio >= 2"));
+    }
+
+    #[test]
+    fn list_fields_match_any_value() {
+        assert!(eval("tags == keep"));
+        assert!(eval("tags != cross-seed"));
+        assert!(!eval("tags != manual"));
+        assert!(eval("tracker contains \"example.org\""));
+    }
+
+    #[test]
+    fn missing_values_compare_false() {
+        assert!(!eval("last_activity > 1d"));
+        assert!(!eval("last_activity <= 1d"));
+    }
+
+    #[test]
+    fn unknown_field_errors() {
+        assert_eq!(
+            "foo == 1".parse::<Expression>(),
+            Err(ExpressionError::UnknownField("foo".to_owned()))
+        );
+    }
+
+    #[test]
+    fn invalid_values_error() {
+        assert!(matches!(
+            "seeding_time > 14".parse::<Expression>(),
+            Err(ExpressionError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            "size > 10XB".parse::<Expression>(),
+            Err(ExpressionError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn unsupported_operator_errors() {
+        assert!(matches!(
+            "ratio contains 1".parse::<Expression>(),
+            Err(ExpressionError::UnsupportedOperator { .. })
+        ));
+        assert!(matches!(
+            "category > \"a\"".parse::<Expression>(),
+            Err(ExpressionError::UnsupportedOperator { .. })
+        ));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(
+            "(ratio > 1".parse::<Expression>(),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            "ratio > 1 ratio".parse::<Expression>(),
+            Err(ExpressionError::UnexpectedToken("ratio".to_owned()))
+        );
+        assert_eq!(
+            "category == \"tv".parse::<Expression>(),
+            Err(ExpressionError::UnterminatedString)
+        );
+    }
+
+    #[test]
+    fn deserialize_reports_parse_errors() {
+        let result: Result<Expression, _> = serde_yaml::from_str("'ratio >>= 1'");
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("invalid expression")
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use std::{str::FromStr, sync::LazyLock, time::Duration};
+
+use chrono::{DateTime, NaiveTime, Offset, TimeZone};
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+/// Upper bound on the occurrences inspected while looking for the next date,
+/// so a window that a cron expression never hits cannot loop forever.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Mixed into the jitter of every occurrence so separate arrmate instances
+/// sharing a schedule do not run at the same moment.
+static JITTER_SALT: LazyLock<u64> = LazyLock::new(|| fastrand::u64(..));
+
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum ScheduleError {
+    #[error("invalid cron expression: {0}")]
+    InvalidCron(String),
+
+    #[error("interval must be greater than zero")]
+    EmptyInterval,
+
+    #[error("unknown timezone '{0}'")]
+    UnknownTimezone(String),
+
+    #[error("invalid window '{0}', expected HH:MM-HH:MM")]
+    InvalidWindow(String),
+
+    #[error("either 'cron' or 'every' must be set")]
+    MissingTrigger,
+
+    #[error("'cron' and 'every' cannot both be set")]
+    ConflictingTriggers,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Trigger {
+    Cron(String),
+    Every(Duration),
+}
+
+/// Time of day during which a schedule may fire. Wraps around midnight when
+/// `start` is after `end`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Window {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl Window {
+    fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Returns `date` when it is inside the window, otherwise the next moment
+    /// the window opens.
+    fn next_opening<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let time = date.time();
+        if self.contains(time) {
+            return Some(date.clone());
+        }
+        let mut day = date.date_naive();
+        if time >= self.start {
+            day = day.succ_opt()?;
+        }
+        let timezone = date.timezone();
+        let opening = day.and_time(self.start);
+        // The opening may fall into a daylight saving gap
+        timezone
+            .from_local_datetime(&opening)
+            .earliest()
+            .or_else(|| {
+                timezone
+                    .from_local_datetime(&(opening + chrono::Duration::hours(1)))
+                    .earliest()
+            })
+    }
+}
+
+impl FromStr for Window {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ScheduleError::InvalidWindow(s.to_owned());
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M");
+        let window = Window {
+            start: parse(start).map_err(|_| invalid())?,
+            end: parse(end).map_err(|_| invalid())?,
+        };
+        if window.start == window.end {
+            return Err(invalid());
+        }
+        Ok(window)
+    }
+}
+
+/// When a task runs: a cron expression or a fixed interval, optionally
+/// delayed by a random jitter and restricted to a daily window. Dates are
+/// computed in `timezone`, or in the host's local timezone when unset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    trigger: Trigger,
+    jitter: Option<Duration>,
+    timezone: Option<chrono_tz::Tz>,
+    window: Option<Window>,
+}
+
+fn to_offset_date_time<Tz: TimeZone>(date: &DateTime<Tz>) -> Option<time::OffsetDateTime> {
+    let offset = time::UtcOffset::from_whole_seconds(date.offset().fix().local_minus_utc()).ok()?;
+    time::OffsetDateTime::from_unix_timestamp(date.timestamp())
+        .ok()
+        .map(|date| date.to_offset(offset))
+}
+
+impl Schedule {
+    pub fn next_date(&self, from: time::OffsetDateTime) -> Option<time::OffsetDateTime> {
+        match &self.timezone {
+            Some(timezone) => self.next_date_in(timezone, from),
+            None => self.next_date_in(&chrono::Local, from),
+        }
+    }
+
+    fn next_date_in<Tz: TimeZone>(
+        &self,
+        timezone: &Tz,
+        from: time::OffsetDateTime,
+    ) -> Option<time::OffsetDateTime> {
+        let jitter = self.jitter.unwrap_or_default();
+        // A cron occurrence just before `from` may still be pending once its
+        // jitter is added, so start looking one jitter earlier.
+        let start = match self.trigger {
+            Trigger::Cron(_) => from - jitter,
+            Trigger::Every(_) => from,
+        };
+        let mut start = timezone.timestamp_opt(start.unix_timestamp(), 0).single()?;
+        for _ in 0..MAX_ITERATIONS {
+            let occurrence = self.next_occurrence(&start)?;
+            let date = to_offset_date_time(&occurrence)? + self.jitter_for(&occurrence);
+            if date > from {
+                return Some(date);
+            }
+            start = occurrence;
+        }
+        None
+    }
+
+    /// Next occurrence of the trigger after `start` that is inside the window.
+    fn next_occurrence<Tz: TimeZone>(&self, start: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let mut occurrence = self.next_trigger(start, false)?;
+        let Some(window) = &self.window else {
+            return Some(occurrence);
+        };
+        for _ in 0..MAX_ITERATIONS {
+            let opening = window.next_opening(&occurrence)?;
+            if opening == occurrence {
+                return Some(occurrence);
+            }
+            occurrence = match self.trigger {
+                Trigger::Cron(_) => self.next_trigger(&opening, true)?,
+                Trigger::Every(_) => opening,
+            };
+        }
+        None
+    }
+
+    fn next_trigger<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        inclusive: bool,
+    ) -> Option<DateTime<Tz>> {
+        match &self.trigger {
+            Trigger::Cron(expression) => croner::Cron::from_str(expression)
+                .ok()?
+                .find_next_occurrence(start, inclusive)
+                .ok(),
+            Trigger::Every(interval) => {
+                Some(start.clone() + chrono::Duration::from_std(*interval).ok()?)
+            }
+        }
+    }
+
+    /// Random delay for `occurrence`, stable across calls so the scheduler
+    /// sees the same date every time it asks.
+    fn jitter_for<Tz: TimeZone>(&self, occurrence: &DateTime<Tz>) -> Duration {
+        let jitter = self.jitter.unwrap_or_default().as_millis() as u64;
+        if jitter == 0 {
+            return Duration::ZERO;
+        }
+        let mut rng = fastrand::Rng::with_seed(occurrence.timestamp() as u64 ^ *JITTER_SALT);
+        Duration::from_millis(rng.u64(0..jitter))
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        croner::Cron::from_str(s).map_err(|e| ScheduleError::InvalidCron(e.to_string()))?;
+        Ok(Schedule {
+            trigger: Trigger::Cron(s.to_owned()),
+            jitter: None,
+            timezone: None,
+            window: None,
+        })
+    }
+}
+
+/// Map form of a schedule in the config file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleSpec {
+    cron: Option<String>,
+    #[serde(with = "humantime_serde::option", default)]
+    every: Option<Duration>,
+    #[serde(with = "humantime_serde::option", default)]
+    jitter: Option<Duration>,
+    timezone: Option<String>,
+    window: Option<String>,
+}
+
+impl TryFrom<ScheduleSpec> for Schedule {
+    type Error = ScheduleError;
+
+    fn try_from(spec: ScheduleSpec) -> Result<Self, Self::Error> {
+        let mut schedule = match (spec.cron, spec.every) {
+            (Some(_), Some(_)) => return Err(ScheduleError::ConflictingTriggers),
+            (None, None) => return Err(ScheduleError::MissingTrigger),
+            (Some(cron), None) => Schedule::from_str(&cron)?,
+            (None, Some(interval)) if interval.is_zero() => {
+                return Err(ScheduleError::EmptyInterval);
+            }
+            (None, Some(interval)) => Schedule {
+                trigger: Trigger::Every(interval),
+                jitter: None,
+                timezone: None,
+                window: None,
+            },
+        };
+        schedule.jitter = spec.jitter.filter(|jitter| !jitter.is_zero());
+        schedule.timezone = spec
+            .timezone
+            .map(|name| {
+                name.parse()
+                    .map_err(|_| ScheduleError::UnknownTimezone(name))
+            })
+            .transpose()?;
+        schedule.window = spec.window.map(|window| window.parse()).transpose()?;
+        Ok(schedule)
+    }
+}
+
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ScheduleVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ScheduleVisitor {
+            type Value = Schedule;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("cron expression or schedule map")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Schedule::from_str(s).map_err(|e| E::custom(format!("invalid schedule: {e}")))
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+            where
+                M: serde::de::MapAccess<'de>,
+            {
+                let spec =
+                    ScheduleSpec::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+                Schedule::try_from(spec)
+                    .map_err(|e| serde::de::Error::custom(format!("invalid schedule: {e}")))
+            }
+        }
+
+        deserializer.deserialize_any(ScheduleVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+    use super::*;
+
+    fn utc(date: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(date, &Rfc3339).unwrap()
+    }
+
+    fn parse(yaml: &str) -> Result<Schedule, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    // ── Deserialization ──────────────────────────────────────────────────────
+
+    #[test]
+    fn cron_string_parses() {
+        let schedule = parse("'0 */5 * * * * *'").unwrap();
+        assert_eq!(
+            schedule.trigger,
+            Trigger::Cron("0 */5 * * * * *".to_owned())
+        );
+    }
+
+    #[test]
+    fn invalid_cron_errors() {
+        assert!(parse("not-a-schedule").is_err());
+        assert!(parse("cron: not-a-schedule").is_err());
+    }
+
+    #[test]
+    fn map_parses_every_option() {
+        let schedule =
+            parse("every: 10m\njitter: 30s\ntimezone: Europe/Berlin\nwindow: '02:00-06:00'")
+                .unwrap();
+        assert_eq!(schedule.trigger, Trigger::Every(Duration::from_secs(600)));
+        assert_eq!(schedule.jitter, Some(Duration::from_secs(30)));
+        assert_eq!(schedule.timezone, Some(chrono_tz::Europe::Berlin));
+        assert_eq!(
+            schedule.window,
+            Some(Window {
+                start: NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn map_requires_exactly_one_trigger() {
+        assert!(parse("jitter: 30s").is_err());
+        assert!(parse("cron: '* * * * *'\nevery: 10m").is_err());
+        assert!(parse("every: 0s").is_err());
+    }
+
+    #[test]
+    fn map_rejects_unknown_timezone_and_window() {
+        assert!(parse("every: 10m\ntimezone: Mars/Olympus").is_err());
+        assert!(parse("every: 10m\nwindow: '02:00'").is_err());
+        assert!(parse("every: 10m\nwindow: '02:00-02:00'").is_err());
+        assert!(parse("every: 10m\nwindows: '02:00-06:00'").is_err());
+    }
+
+    // ── next_date ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn every_adds_interval() {
+        let schedule = parse("every: 10m").unwrap();
+        let from = utc("2024-01-01T12:00:00Z");
+        assert_eq!(schedule.next_date(from), Some(utc("2024-01-01T12:10:00Z")));
+    }
+
+    #[test]
+    fn cron_uses_timezone() {
+        let schedule = parse("cron: '0 0 3 * * *'\ntimezone: America/New_York").unwrap();
+        let from = utc("2024-01-01T00:00:00Z");
+        // 03:00 in New York is 08:00 UTC in winter
+        assert_eq!(schedule.next_date(from), Some(utc("2024-01-01T08:00:00Z")));
+        assert_eq!(
+            schedule.next_date(from).unwrap().offset(),
+            time::UtcOffset::from_hms(-5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn every_waits_for_window() {
+        let schedule = parse("every: 10m\ntimezone: UTC\nwindow: '02:00-06:00'").unwrap();
+        assert_eq!(
+            schedule.next_date(utc("2024-01-01T12:00:00Z")),
+            Some(utc("2024-01-02T02:00:00Z"))
+        );
+        assert_eq!(
+            schedule.next_date(utc("2024-01-01T03:00:00Z")),
+            Some(utc("2024-01-01T03:10:00Z"))
+        );
+    }
+
+    #[test]
+    fn cron_skips_occurrences_outside_window() {
+        let schedule = parse("cron: '0 15 * * * *'\ntimezone: UTC\nwindow: '22:00-01:00'").unwrap();
+        assert_eq!(
+            schedule.next_date(utc("2024-01-01T12:00:00Z")),
+            Some(utc("2024-01-01T22:15:00Z"))
+        );
+        assert_eq!(
+            schedule.next_date(utc("2024-01-01T23:30:00Z")),
+            Some(utc("2024-01-02T00:15:00Z"))
+        );
+    }
+
+    #[test]
+    fn jitter_is_bounded_and_stable() {
+        let schedule = parse("cron: '0 0 * * * *'\njitter: 5m\ntimezone: UTC").unwrap();
+        let from = utc("2024-01-01T12:30:00Z");
+        let next = schedule.next_date(from).unwrap();
+        assert!(next >= utc("2024-01-01T13:00:00Z"));
+        assert!(next < utc("2024-01-01T13:05:00Z"));
+        assert_eq!(schedule.next_date(from), Some(next));
+    }
+
+    #[test]
+    fn jittered_occurrence_is_not_skipped() {
+        let schedule = parse("cron: '0 0 * * * *'\njitter: 5m\ntimezone: UTC").unwrap();
+        let next = schedule.next_date(utc("2024-01-01T12:30:00Z")).unwrap();
+        // Asking again after the hour but before the delayed run still finds it
+        let from = utc("2024-01-01T13:00:00Z");
+        if next > from {
+            assert_eq!(schedule.next_date(from), Some(next));
+        }
+    }
+
+    #[test]
+    fn local_timezone_is_used_by_default() {
+        let schedule: Schedule = "0 * * * * * *".parse().unwrap();
+        let now = OffsetDateTime::now_utc();
+        let next = schedule.next_date(now).unwrap();
+        assert!(next > now);
+        assert!(next - now <= time::Duration::seconds(60));
+    }
+}
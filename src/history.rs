@@ -0,0 +1,336 @@
+use std::{path::Path, sync::Mutex, time::Duration};
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+use time::OffsetDateTime;
+
+use crate::tasks::ItemAction;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    task TEXT NOT NULL,
+    started_at INTEGER NOT NULL,
+    finished_at INTEGER NOT NULL,
+    success INTEGER NOT NULL,
+    error TEXT,
+    affected INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS runs_task_started_at ON runs (task, started_at);
+CREATE TABLE IF NOT EXISTS actions (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+    item_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    action TEXT NOT NULL,
+    reason TEXT
+);
+CREATE INDEX IF NOT EXISTS actions_run_id ON actions (run_id);
+";
+
+/// A single execution of a task and what it did.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RunRecord {
+    pub task: String,
+    pub started_at: OffsetDateTime,
+    pub finished_at: OffsetDateTime,
+    pub error: Option<String>,
+    pub actions: Vec<ItemAction>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct HistoryFilter {
+    pub task: Option<String>,
+    /// Only runs started at or after this moment
+    pub since: Option<OffsetDateTime>,
+    /// Only runs started before this moment
+    pub until: Option<OffsetDateTime>,
+    pub limit: usize,
+}
+
+impl Default for HistoryFilter {
+    fn default() -> Self {
+        Self {
+            task: None,
+            since: None,
+            until: None,
+            limit: 50,
+        }
+    }
+}
+
+/// Task run history stored in a SQLite database.
+pub struct History {
+    connection: Mutex<Connection>,
+    /// Runs started longer ago are pruned whenever a run is recorded
+    retention: Duration,
+}
+
+fn to_timestamp(moment: OffsetDateTime) -> i64 {
+    moment.unix_timestamp()
+}
+
+fn from_timestamp(timestamp: i64) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::UNIX_EPOCH)
+}
+
+impl History {
+    pub fn open(path: &Path, retention: Duration) -> Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("Could not open history database {}", path.display()))?;
+        Self::with_connection(connection, retention)
+    }
+
+    fn with_connection(connection: Connection, retention: Duration) -> Result<Self> {
+        connection
+            .execute_batch(SCHEMA)
+            .context("Could not create history tables")?;
+        // Databases created before reasons were recorded lack the column
+        let has_reason: bool = connection.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('actions') WHERE name = 'reason'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_reason {
+            connection
+                .execute("ALTER TABLE actions ADD COLUMN reason TEXT", [])
+                .context("Could not add reasons to the history")?;
+        }
+        Ok(Self {
+            connection: Mutex::new(connection),
+            retention,
+        })
+    }
+
+    pub fn record(&self, run: &RunRecord) -> Result<()> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO runs (task, started_at, finished_at, success, error, affected)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run.task,
+                to_timestamp(run.started_at),
+                to_timestamp(run.finished_at),
+                run.error.is_none(),
+                run.error,
+                run.actions.len() as i64,
+            ],
+        )?;
+        let run_id = transaction.last_insert_rowid();
+        for action in &run.actions {
+            transaction.execute(
+                "INSERT INTO actions (run_id, item_id, name, action, reason)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![run_id, action.id, action.name, action.action, action.reason],
+            )?;
+        }
+        // Foreign keys are not enforced, so the actions are deleted first
+        let cutoff = to_timestamp(OffsetDateTime::now_utc() - self.retention);
+        transaction.execute(
+            "DELETE FROM actions WHERE run_id IN (SELECT id FROM runs WHERE started_at < ?1)",
+            params![cutoff],
+        )?;
+        transaction.execute("DELETE FROM runs WHERE started_at < ?1", params![cutoff])?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Returns the runs matching `filter`, most recent first.
+    pub fn runs(&self, filter: &HistoryFilter) -> Result<Vec<RunRecord>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT id, task, started_at, finished_at, error FROM runs
+             WHERE (?1 IS NULL OR task = ?1)
+               AND (?2 IS NULL OR started_at >= ?2)
+               AND (?3 IS NULL OR started_at < ?3)
+             ORDER BY started_at DESC, id DESC
+             LIMIT ?4",
+        )?;
+        let rows = statement.query_map(
+            params![
+                filter.task,
+                filter.since.map(to_timestamp),
+                filter.until.map(to_timestamp),
+                filter.limit as i64,
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    RunRecord {
+                        task: row.get(1)?,
+                        started_at: from_timestamp(row.get(2)?),
+                        finished_at: from_timestamp(row.get(3)?),
+                        error: row.get(4)?,
+                        actions: Vec::new(),
+                    },
+                ))
+            },
+        )?;
+
+        let mut actions_statement = connection.prepare(
+            "SELECT item_id, name, action, reason FROM actions WHERE run_id = ?1 ORDER BY id",
+        )?;
+        let mut runs = Vec::new();
+        for row in rows {
+            let (run_id, mut run) = row?;
+            run.actions = actions_statement
+                .query_map(params![run_id], |row| {
+                    Ok(ItemAction {
+                        id: row.get(0)?,
+                        name: row.get(1)?,
+                        action: row.get(2)?,
+                        reason: row.get(3)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<_>>()?;
+            runs.push(run);
+        }
+        Ok(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const RETENTION: Duration = Duration::from_secs(90 * 24 * 3600);
+
+    fn make_history() -> History {
+        History::with_connection(Connection::open_in_memory().unwrap(), RETENTION).unwrap()
+    }
+
+    fn make_run(task: &str, started_secs_ago: u64, error: Option<&str>) -> RunRecord {
+        let started_at = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap()
+            - Duration::from_secs(started_secs_ago);
+        RunRecord {
+            task: task.to_string(),
+            started_at,
+            finished_at: started_at + Duration::from_secs(1),
+            error: error.map(str::to_owned),
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn record_and_read_back_with_actions() {
+        let history = make_history();
+        let mut run = make_run("cleanup", 10, None);
+        run.actions = vec![
+            ItemAction {
+                id: "abc".to_string(),
+                name: "Some.Torrent".to_string(),
+                action: "delete".to_string(),
+                reason: None,
+            },
+            ItemAction {
+                id: "def".to_string(),
+                name: "Other.Torrent".to_string(),
+                action: "remove_and_blocklist".to_string(),
+                reason: Some("stalled (5/5 strikes)".to_string()),
+            },
+        ];
+        history.record(&run).unwrap();
+        let runs = history.runs(&HistoryFilter::default()).unwrap();
+        assert_eq!(runs, vec![run]);
+    }
+
+    #[test]
+    fn reason_column_added_to_existing_database() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE actions (
+                    id INTEGER PRIMARY KEY,
+                    run_id INTEGER NOT NULL,
+                    item_id TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    action TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+        let history = History::with_connection(connection, RETENTION).unwrap();
+        let mut run = make_run("retry", 10, None);
+        run.actions = vec![ItemAction {
+            id: "def".to_string(),
+            name: "Other.Torrent".to_string(),
+            action: "remove".to_string(),
+            reason: Some("stuck".to_string()),
+        }];
+        history.record(&run).unwrap();
+        assert_eq!(history.runs(&HistoryFilter::default()).unwrap(), vec![run]);
+    }
+
+    #[test]
+    fn runs_are_most_recent_first() {
+        let history = make_history();
+        history.record(&make_run("cleanup", 100, None)).unwrap();
+        history.record(&make_run("cleanup", 10, None)).unwrap();
+        let runs = history.runs(&HistoryFilter::default()).unwrap();
+        assert!(runs[0].started_at > runs[1].started_at);
+    }
+
+    #[test]
+    fn runs_filter_by_task_and_date() {
+        let history = make_history();
+        history.record(&make_run("cleanup", 7200, None)).unwrap();
+        history.record(&make_run("cleanup", 60, None)).unwrap();
+        history.record(&make_run("retry", 60, None)).unwrap();
+
+        let filter = HistoryFilter {
+            task: Some("cleanup".to_string()),
+            since: Some(OffsetDateTime::now_utc() - Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        let runs = history.runs(&filter).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].task, "cleanup");
+
+        let filter = HistoryFilter {
+            until: Some(OffsetDateTime::now_utc() - Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        assert_eq!(history.runs(&filter).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn record_prunes_runs_older_than_retention() {
+        let history = make_history();
+        let mut old = make_run("cleanup", RETENTION.as_secs() + 60, None);
+        old.actions = vec![ItemAction {
+            id: "abc".to_string(),
+            name: "Some.Torrent".to_string(),
+            action: "delete".to_string(),
+            reason: None,
+        }];
+        history.record(&old).unwrap();
+        let recent = make_run("cleanup", 60, None);
+        history.record(&recent).unwrap();
+        assert_eq!(
+            history.runs(&HistoryFilter::default()).unwrap(),
+            vec![recent]
+        );
+        let actions: i64 = history
+            .connection
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM actions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(actions, 0);
+    }
+
+    #[test]
+    fn runs_respect_limit() {
+        let history = make_history();
+        for i in 0..5 {
+            history.record(&make_run("cleanup", i, None)).unwrap();
+        }
+        let filter = HistoryFilter {
+            limit: 2,
+            ..Default::default()
+        };
+        assert_eq!(history.runs(&filter).unwrap().len(), 2);
+    }
+}
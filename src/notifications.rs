@@ -0,0 +1,154 @@
+use anyhow::{Context, Result, bail};
+use log::{error, info};
+use tokio::{process::Command, sync::mpsc, task::JoinHandle};
+
+use crate::config::NotificationConfig;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Notification {
+    pub title: String,
+    pub message: String,
+}
+
+/// Queues notifications for a background worker that runs the configured
+/// command for each of them, one at a time. The worker exits once every
+/// `Notifier` is dropped and the queue is empty.
+#[derive(Clone)]
+pub struct Notifier {
+    sender: mpsc::UnboundedSender<Notification>,
+}
+
+impl Notifier {
+    pub fn spawn(config: NotificationConfig) -> (Self, JoinHandle<()>) {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(async move {
+            while let Some(notification) = receiver.recv().await {
+                if let Err(e) = deliver(&config, &notification).await {
+                    error!(
+                        "Could not send notification '{}': {e:#}",
+                        notification.title
+                    );
+                }
+            }
+        });
+        (Self { sender }, handle)
+    }
+
+    /// Returns a notifier whose notifications end up in the returned receiver.
+    #[cfg(test)]
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<Notification>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self { sender }, receiver)
+    }
+
+    pub fn send(&self, title: String, message: String) {
+        info!("Notification: {title}: {message}");
+        if self.sender.send(Notification { title, message }).is_err() {
+            error!("Notification worker is gone, notification dropped");
+        }
+    }
+}
+
+async fn deliver(config: &NotificationConfig, notification: &Notification) -> Result<()> {
+    let Some((program, args)) = config.command.split_first() else {
+        bail!("No notification command configured");
+    };
+    let output = tokio::time::timeout(
+        config.timeout,
+        Command::new(program)
+            .args(args)
+            .env("ARRMATE_TITLE", &notification.title)
+            .env("ARRMATE_MESSAGE", &notification.message)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .with_context(|| format!("'{program}' timed out"))?
+    .with_context(|| format!("Could not run '{program}'"))?;
+    if !output.status.success() {
+        bail!(
+            "'{program}' failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn shell(script: &str) -> NotificationConfig {
+        NotificationConfig {
+            command: vec!["sh".to_owned(), "-c".to_owned(), script.to_owned()],
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    fn notification() -> Notification {
+        Notification {
+            title: "Giving up".to_owned(),
+            message: "Some.Show.S01E01".to_owned(),
+        }
+    }
+
+    // ── deliver ──────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn deliver_passes_notification_in_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out");
+        let config = shell(&format!(
+            "echo \"$ARRMATE_TITLE|$ARRMATE_MESSAGE\" > {}",
+            path.display()
+        ));
+        deliver(&config, &notification()).await.unwrap();
+        let written = std::fs::read_to_string(path).unwrap();
+        assert_eq!(written.trim(), "Giving up|Some.Show.S01E01");
+    }
+
+    #[tokio::test]
+    async fn deliver_reports_failures() {
+        let error = deliver(&shell("echo broken >&2; exit 3"), &notification())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("broken"));
+
+        let config = NotificationConfig {
+            command: vec![],
+            timeout: Duration::from_secs(5),
+        };
+        assert!(deliver(&config, &notification()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn deliver_times_out() {
+        let config = NotificationConfig {
+            timeout: Duration::from_millis(50),
+            ..shell("sleep 5")
+        };
+        let error = deliver(&config, &notification()).await.unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+    }
+
+    // ── Notifier ─────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn worker_flushes_queue_when_notifiers_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out");
+        let (notifier, handle) = Notifier::spawn(shell(&format!(
+            "echo \"$ARRMATE_MESSAGE\" >> {}",
+            path.display()
+        )));
+        notifier.send("a".to_owned(), "first".to_owned());
+        notifier.send("b".to_owned(), "second".to_owned());
+        drop(notifier);
+        handle.await.unwrap();
+        let written = std::fs::read_to_string(path).unwrap();
+        assert_eq!(written, "first\nsecond\n");
+    }
+}
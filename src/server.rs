@@ -0,0 +1,809 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use http_body_util::Full;
+use hyper::{
+    HeaderMap, Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    header,
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use log::{error, info};
+use serde::Serialize;
+use time::OffsetDateTime;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+use url::{Host, Url};
+
+use crate::{
+    apis::{QBittorrentAPIInterface, SonarrAndRadarrAPIInterface, UsenetAPIInterface},
+    config::ServerConfig,
+    tasks::TaskReport,
+};
+
+mod dashboard;
+
+/// Number of past runs kept per task.
+const MAX_TASK_HISTORY: usize = 20;
+
+const SERVICE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait before accepting connections again after a failure, such
+/// as running out of file descriptors.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// How long the scheduler may go without a heartbeat before it is considered stalled.
+const SCHEDULER_STALL_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Client used to tell whether a configured service is reachable.
+#[derive(Clone)]
+pub enum ServiceClient {
+    QBittorrent(Arc<dyn QBittorrentAPIInterface>),
+    Arr(Arc<dyn SonarrAndRadarrAPIInterface>),
+    Usenet(Arc<dyn UsenetAPIInterface>),
+}
+
+impl ServiceClient {
+    async fn check(&self) -> Result<()> {
+        let check = async {
+            match self {
+                ServiceClient::QBittorrent(api) => api.get_version().await.map(|_| ()),
+                ServiceClient::Arr(api) => api.get_system_status().await.map(|_| ()),
+                ServiceClient::Usenet(api) => api.get_version().await.map(|_| ()),
+            }
+        };
+        tokio::time::timeout(SERVICE_CHECK_TIMEOUT, check)
+            .await
+            .context("Timed out")?
+    }
+}
+
+#[derive(Clone)]
+pub struct Service {
+    pub name: String,
+    pub host: String,
+    pub client: ServiceClient,
+}
+
+/// Result of the last execution of a task.
+#[derive(Clone, Serialize, Debug)]
+pub struct TaskRun {
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct TaskStatus {
+    pub name: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub next_date: Option<OffsetDateTime>,
+    pub running: bool,
+    pub last_run: Option<TaskRun>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct SchedulerHealth {
+    pub alive: bool,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_heartbeat: Option<OffsetDateTime>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct TaskHealth {
+    pub name: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_success: Option<OffsetDateTime>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct ServiceHealth {
+    pub name: String,
+    pub host: String,
+    pub reachable: bool,
+    pub error: Option<String>,
+}
+
+/// Body of the `/healthz` and `/readyz` endpoints.
+#[derive(Clone, Serialize, Debug)]
+pub struct HealthReport {
+    /// The scheduler loop is running
+    pub live: bool,
+    /// The scheduler is running and every configured service is reachable,
+    /// left out of liveness reports which don't check the services
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ready: Option<bool>,
+    pub scheduler: SchedulerHealth,
+    pub tasks: Vec<TaskHealth>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<ServiceHealth>,
+}
+
+/// Requests sent from the HTTP API to the scheduler.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Command {
+    RunTask(String),
+    ReloadConfig,
+}
+
+/// What the scheduler publishes for the HTTP API to serve.
+#[derive(Default)]
+pub struct ServerState {
+    services: RwLock<Vec<Service>>,
+    tasks: RwLock<Vec<TaskStatus>>,
+    runs: RwLock<HashMap<String, VecDeque<TaskRun>>>,
+    reports: RwLock<HashMap<String, TaskReport>>,
+    heartbeat: RwLock<Option<OffsetDateTime>>,
+    last_success: RwLock<HashMap<String, OffsetDateTime>>,
+}
+
+impl ServerState {
+    /// Replaces the list of configured services.
+    pub fn set_services(&self, services: Vec<Service>) {
+        *self.services.write().unwrap() = services;
+    }
+
+    /// Replaces the list of tasks, when they run next and whether they are running.
+    pub fn set_tasks(&self, tasks: Vec<(String, Option<OffsetDateTime>, bool)>) {
+        let runs = self.runs.read().unwrap();
+        *self.tasks.write().unwrap() = tasks
+            .into_iter()
+            .map(|(name, next_date, running)| TaskStatus {
+                last_run: runs.get(&name).and_then(|runs| runs.back()).cloned(),
+                name,
+                next_date,
+                running,
+            })
+            .collect();
+    }
+
+    /// Marks the scheduler loop as alive.
+    pub fn heartbeat(&self) {
+        *self.heartbeat.write().unwrap() = Some(OffsetDateTime::now_utc());
+    }
+
+    /// Records the result and report of an execution of the task `name`.
+    pub fn record_run(&self, name: &str, run: TaskRun, report: Option<TaskReport>) {
+        if run.error.is_none() {
+            self.last_success
+                .write()
+                .unwrap()
+                .insert(name.to_owned(), run.finished_at);
+        }
+        for task in self.tasks.write().unwrap().iter_mut() {
+            if task.name == name {
+                task.last_run = Some(run.clone());
+            }
+        }
+        let mut runs = self.runs.write().unwrap();
+        let history = runs.entry(name.to_owned()).or_default();
+        history.push_back(run);
+        if history.len() > MAX_TASK_HISTORY {
+            history.pop_front();
+        }
+        drop(runs);
+        if let Some(report) = report {
+            self.reports
+                .write()
+                .unwrap()
+                .insert(name.to_owned(), report);
+        }
+    }
+
+    pub fn tasks(&self) -> Vec<TaskStatus> {
+        self.tasks.read().unwrap().clone()
+    }
+
+    /// Returns the past runs of the task `name`, most recent first.
+    pub fn history(&self, name: &str) -> Vec<TaskRun> {
+        self.runs
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|runs| runs.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn report(&self, name: &str) -> Option<TaskReport> {
+        self.reports.read().unwrap().get(name).cloned()
+    }
+
+    /// Checks every configured service and returns whether it is reachable.
+    pub async fn check_services(&self) -> Vec<(Service, Result<()>)> {
+        let services = self.services.read().unwrap().clone();
+        let mut set = tokio::task::JoinSet::new();
+        for (index, service) in services.into_iter().enumerate() {
+            set.spawn(async move {
+                let result = service.client.check().await;
+                (index, service, result)
+            });
+        }
+
+        let mut results = Vec::new();
+        while let Some(join_result) = set.join_next().await {
+            if let Ok(result) = join_result {
+                results.push(result);
+            }
+        }
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, service, result)| (service, result))
+            .collect()
+    }
+
+    /// Reports scheduler liveness and the last successful run of each task,
+    /// and with `check_services` the connectivity of every configured service.
+    pub async fn health(&self, check_services: bool) -> HealthReport {
+        let last_heartbeat = *self.heartbeat.read().unwrap();
+        let alive = last_heartbeat.is_some_and(|heartbeat| {
+            OffsetDateTime::now_utc() - heartbeat < SCHEDULER_STALL_TIMEOUT
+        });
+        let tasks = {
+            let last_success = self.last_success.read().unwrap();
+            self.tasks()
+                .into_iter()
+                .map(|task| TaskHealth {
+                    last_success: last_success.get(&task.name).copied(),
+                    name: task.name,
+                })
+                .collect()
+        };
+        let checked = if check_services {
+            self.check_services().await
+        } else {
+            Vec::new()
+        };
+        let services: Vec<ServiceHealth> = checked
+            .into_iter()
+            .map(|(service, result)| ServiceHealth {
+                name: service.name,
+                host: service.host,
+                reachable: result.is_ok(),
+                error: result.err().map(|e| format!("{e:#}")),
+            })
+            .collect();
+        HealthReport {
+            live: alive,
+            ready: check_services
+                .then(|| alive && services.iter().all(|service| service.reachable)),
+            scheduler: SchedulerHealth {
+                alive,
+                last_heartbeat,
+            },
+            tasks,
+            services,
+        }
+    }
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Full<Bytes>> {
+    match serde_json::to_vec(body) {
+        Ok(body) => Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap(),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+fn html_response(body: String) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+        .body(Full::new(Bytes::from(body)))
+        .unwrap()
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    json_response(status, &serde_json::json!({ "error": message }))
+}
+
+/// Serves `/healthz` (liveness) when `ready` is false and `/readyz` otherwise.
+/// Liveness only looks at the scheduler heartbeat, so probing it is cheap.
+async fn health_response(state: &ServerState, ready: bool) -> Response<Full<Bytes>> {
+    let report = state.health(ready).await;
+    let healthy = report.ready.unwrap_or(report.live);
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    json_response(status, &report)
+}
+
+fn send_command(commands: &mpsc::Sender<Command>, command: Command) -> Response<Full<Bytes>> {
+    match commands.try_send(command) {
+        Ok(()) => json_response(StatusCode::ACCEPTED, &serde_json::json!({ "queued": true })),
+        Err(e) => error_response(StatusCode::SERVICE_UNAVAILABLE, &e.to_string()),
+    }
+}
+
+/// Whether the `Host` header `host` names this server: localhost, or an IP
+/// address it listens on. Any other name could point at it through DNS
+/// rebinding.
+fn allowed_host(address: SocketAddr, host: &str) -> bool {
+    let Ok(url) = Url::parse(&format!("http://{host}")) else {
+        return false;
+    };
+    let ip: IpAddr = match url.host() {
+        Some(Host::Domain(domain)) => return domain == "localhost",
+        Some(Host::Ipv4(ip)) => ip.into(),
+        Some(Host::Ipv6(ip)) => ip.into(),
+        None => return false,
+    };
+    ip.is_loopback() || address.ip().is_unspecified() || ip == address.ip()
+}
+
+/// Rejects requests from web pages of other sites, and requests that start or
+/// change anything without the configured token.
+fn guard(
+    config: &ServerConfig,
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+) -> Option<Response<Full<Bytes>>> {
+    let Some(host) = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .filter(|host| allowed_host(config.address, host))
+    else {
+        return Some(error_response(StatusCode::FORBIDDEN, "host not allowed"));
+    };
+
+    // Browsers send a plain cross-site POST without a preflight
+    if let Some(origin) = headers.get(header::ORIGIN)
+        && !origin.is_empty()
+        && origin != format!("http://{host}").as_str()
+        && origin != format!("https://{host}").as_str()
+    {
+        return Some(error_response(StatusCode::FORBIDDEN, "origin not allowed"));
+    }
+
+    if method == Method::POST
+        && path.starts_with("/api/")
+        && let Some(token) = &config.token
+    {
+        let authorized = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|value| value == token);
+        if !authorized {
+            return Some(error_response(StatusCode::UNAUTHORIZED, "invalid token"));
+        }
+    }
+
+    None
+}
+
+/// Routes a request of the control API.
+fn handle(
+    method: &Method,
+    path: &str,
+    state: &ServerState,
+    commands: &mpsc::Sender<Command>,
+) -> Response<Full<Bytes>> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        (&Method::GET, ["api", "tasks"]) => json_response(StatusCode::OK, &state.tasks()),
+        (&Method::POST, ["api", "tasks", name, "run"]) => {
+            if state.tasks().iter().any(|task| task.name == *name) {
+                send_command(commands, Command::RunTask((*name).to_owned()))
+            } else {
+                error_response(StatusCode::NOT_FOUND, &format!("unknown task '{name}'"))
+            }
+        }
+        (&Method::GET, ["api", "cleanup"]) => match state.report("cleanup") {
+            Some(report) => json_response(StatusCode::OK, &report),
+            None => error_response(StatusCode::NOT_FOUND, "cleanup has not run yet"),
+        },
+        (&Method::GET, ["api", "retry", "strikes"]) => match state.report("retry") {
+            Some(TaskReport::Retry { strikes, .. }) => json_response(StatusCode::OK, &strikes),
+            _ => error_response(StatusCode::NOT_FOUND, "retry has not run yet"),
+        },
+        (&Method::POST, ["api", "config", "reload"]) => {
+            send_command(commands, Command::ReloadConfig)
+        }
+        _ => error_response(StatusCode::NOT_FOUND, "not found"),
+    }
+}
+
+/// Serves the control API on the configured address until the task is
+/// aborted.
+pub async fn serve(
+    config: ServerConfig,
+    state: Arc<ServerState>,
+    commands: mpsc::Sender<Command>,
+) -> Result<()> {
+    let address = config.address;
+    let listener = TcpListener::bind(address)
+        .await
+        .with_context(|| format!("Could not listen on {address}"))?;
+    info!("HTTP API listening on {address}");
+
+    let config = Arc::new(config);
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                error!("Could not accept HTTP connection: {e}");
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let config = config.clone();
+        let state = state.clone();
+        let commands = commands.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request: Request<Incoming>| {
+                let config = config.clone();
+                let state = state.clone();
+                let commands = commands.clone();
+                async move {
+                    let (method, path) = (request.method(), request.uri().path());
+                    let response = match guard(&config, method, path, request.headers()) {
+                        Some(rejection) => rejection,
+                        None => match (method, path) {
+                            (&Method::GET, "/") => html_response(dashboard::render(&state).await),
+                            (&Method::GET, "/healthz") => health_response(&state, false).await,
+                            (&Method::GET, "/readyz") => health_response(&state, true).await,
+                            (method, path) => handle(method, path, &state, &commands),
+                        },
+                    };
+                    Ok::<_, Infallible>(response)
+                }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                error!("HTTP connection error: {e}");
+            }
+        });
+    }
+}
+
+/// Requests `path` from the HTTP API listening on `address` and returns the
+/// status code and body of the response.
+pub async fn probe(address: SocketAddr, path: &str) -> Result<(u16, String)> {
+    let mut address = address;
+    if address.ip().is_unspecified() {
+        address.set_ip(match address {
+            SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+
+    let request = async {
+        let mut stream = TcpStream::connect(address)
+            .await
+            .with_context(|| format!("Could not connect to {address}"))?;
+        stream
+            .write_all(
+                format!("GET {path} HTTP/1.1\r\nHost: {address}\r\nConnection: close\r\n\r\n")
+                    .as_bytes(),
+            )
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        anyhow::Ok(response)
+    };
+    let response = tokio::time::timeout(PROBE_TIMEOUT, request)
+        .await
+        .context("Timed out")??;
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .with_context(|| format!("Invalid HTTP response from {address}"))?;
+    Ok((status, body.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    use super::*;
+
+    async fn body_json(response: Response<Full<Bytes>>) -> serde_json::Value {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn make_state() -> ServerState {
+        let state = ServerState::default();
+        state.set_tasks(vec![
+            ("cleanup".to_string(), None, false),
+            ("retry".to_string(), None, false),
+        ]);
+        state
+    }
+
+    fn make_run(error: Option<&str>) -> TaskRun {
+        TaskRun {
+            started_at: OffsetDateTime::now_utc(),
+            finished_at: OffsetDateTime::now_utc(),
+            error: error.map(str::to_owned),
+        }
+    }
+
+    // ── ServerState ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn record_run_survives_task_reload() {
+        let state = make_state();
+        state.record_run("cleanup", make_run(Some("boom")), None);
+        state.set_tasks(vec![("cleanup".to_string(), None, false)]);
+        let tasks = state.tasks();
+        assert_eq!(
+            tasks[0].last_run.as_ref().unwrap().error.as_deref(),
+            Some("boom")
+        );
+    }
+
+    #[test]
+    fn history_is_most_recent_first_and_bounded() {
+        let state = make_state();
+        for i in 0..MAX_TASK_HISTORY + 5 {
+            state.record_run("cleanup", make_run(Some(&i.to_string())), None);
+        }
+        let history = state.history("cleanup");
+        assert_eq!(history.len(), MAX_TASK_HISTORY);
+        let expected = (MAX_TASK_HISTORY + 4).to_string();
+        assert_eq!(history[0].error.as_deref(), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn record_run_tracks_last_success() {
+        let state = make_state();
+        let success = make_run(None);
+        state.record_run("cleanup", success.clone(), None);
+        state.record_run("cleanup", make_run(Some("boom")), None);
+        assert_eq!(
+            state.last_success.read().unwrap().get("cleanup"),
+            Some(&success.finished_at)
+        );
+    }
+
+    // ── health ───────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn health_without_heartbeat_is_not_live() {
+        let report = make_state().health(true).await;
+        assert!(!report.live);
+        assert_eq!(report.ready, Some(false));
+        assert!(report.scheduler.last_heartbeat.is_none());
+    }
+
+    #[tokio::test]
+    async fn health_after_heartbeat_is_live_and_ready() {
+        let state = make_state();
+        state.heartbeat();
+        state.record_run("cleanup", make_run(None), None);
+        let report = state.health(true).await;
+        assert!(report.live);
+        assert_eq!(report.ready, Some(true));
+        assert!(report.tasks[0].last_success.is_some());
+        assert!(report.tasks[1].last_success.is_none());
+    }
+
+    #[tokio::test]
+    async fn health_with_stale_heartbeat_is_not_live() {
+        let state = make_state();
+        *state.heartbeat.write().unwrap() =
+            Some(OffsetDateTime::now_utc() - SCHEDULER_STALL_TIMEOUT);
+        assert!(!state.health(false).await.live);
+    }
+
+    #[tokio::test]
+    async fn liveness_does_not_check_services() {
+        let state = make_state();
+        state.heartbeat();
+        let report = state.health(false).await;
+        assert!(report.live);
+        assert_eq!(report.ready, None);
+        assert!(report.services.is_empty());
+    }
+
+    #[tokio::test]
+    async fn health_response_status_follows_report() {
+        let state = make_state();
+        let response = health_response(&state, false).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        state.heartbeat();
+        let response = health_response(&state, true).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body["tasks"][0]["name"], "cleanup");
+    }
+
+    // ── probe ────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn probe_reads_status_and_body() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 1024];
+            let _ = stream.read(&mut buffer).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 2\r\n\r\n{}")
+                .await
+                .unwrap();
+        });
+        let (status, body) = probe(address, "/readyz").await.unwrap();
+        assert_eq!(status, 503);
+        assert_eq!(body, "{}");
+    }
+
+    #[tokio::test]
+    async fn probe_fails_when_nothing_listens() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        assert!(probe(address, "/readyz").await.is_err());
+    }
+
+    // ── guard ────────────────────────────────────────────────────────────────
+
+    fn make_config(address: &str, token: Option<&str>) -> ServerConfig {
+        ServerConfig {
+            address: address.parse().unwrap(),
+            token: token.map(str::to_owned),
+        }
+    }
+
+    fn make_headers(headers: &[(header::HeaderName, &str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn allowed_host_accepts_localhost_and_bound_address() {
+        let local: SocketAddr = "127.0.0.1:8686".parse().unwrap();
+        assert!(allowed_host(local, "localhost:8686"));
+        assert!(allowed_host(local, "127.0.0.1:8686"));
+        assert!(allowed_host(local, "[::1]:8686"));
+        assert!(!allowed_host(local, "192.168.1.10:8686"));
+        assert!(!allowed_host(local, "attacker.example.com:8686"));
+
+        let any: SocketAddr = "0.0.0.0:8686".parse().unwrap();
+        assert!(allowed_host(any, "192.168.1.10:8686"));
+        assert!(!allowed_host(any, "attacker.example.com"));
+    }
+
+    #[test]
+    fn guard_rejects_missing_or_foreign_host() {
+        let config = make_config("127.0.0.1:8686", None);
+        let response = guard(&config, &Method::GET, "/api/tasks", &HeaderMap::new());
+        assert_eq!(response.unwrap().status(), StatusCode::FORBIDDEN);
+        let headers = make_headers(&[(header::HOST, "rebound.example.com:8686")]);
+        let response = guard(&config, &Method::GET, "/api/tasks", &headers);
+        assert_eq!(response.unwrap().status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn guard_rejects_cross_origin_requests() {
+        let config = make_config("127.0.0.1:8686", None);
+        let headers = make_headers(&[
+            (header::HOST, "localhost:8686"),
+            (header::ORIGIN, "https://evil.example.com"),
+        ]);
+        let response = guard(&config, &Method::POST, "/api/config/reload", &headers);
+        assert_eq!(response.unwrap().status(), StatusCode::FORBIDDEN);
+
+        let headers = make_headers(&[
+            (header::HOST, "localhost:8686"),
+            (header::ORIGIN, "http://localhost:8686"),
+        ]);
+        assert!(guard(&config, &Method::POST, "/api/config/reload", &headers).is_none());
+    }
+
+    #[test]
+    fn guard_requires_token_for_api_posts() {
+        let config = make_config("127.0.0.1:8686", Some("secret"));
+        let headers = make_headers(&[(header::HOST, "127.0.0.1:8686")]);
+        assert!(guard(&config, &Method::GET, "/api/tasks", &headers).is_none());
+        let response = guard(&config, &Method::POST, "/api/tasks/cleanup/run", &headers);
+        assert_eq!(response.unwrap().status(), StatusCode::UNAUTHORIZED);
+
+        let headers = make_headers(&[
+            (header::HOST, "127.0.0.1:8686"),
+            (header::AUTHORIZATION, "Bearer secret"),
+        ]);
+        assert!(guard(&config, &Method::POST, "/api/tasks/cleanup/run", &headers).is_none());
+    }
+
+    // ── handle ───────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn handle_lists_tasks() {
+        let (tx, _rx) = mpsc::channel(1);
+        let response = handle(&Method::GET, "/api/tasks", &make_state(), &tx);
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body[0]["name"], "cleanup");
+        assert_eq!(body[1]["name"], "retry");
+    }
+
+    #[tokio::test]
+    async fn handle_run_task_sends_command() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let response = handle(&Method::POST, "/api/tasks/cleanup/run", &make_state(), &tx);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            Command::RunTask("cleanup".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn handle_run_unknown_task_is_not_found() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let response = handle(&Method::POST, "/api/tasks/nope/run", &make_state(), &tx);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn handle_reload_sends_command() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let response = handle(&Method::POST, "/api/config/reload", &make_state(), &tx);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert_eq!(rx.try_recv().unwrap(), Command::ReloadConfig);
+    }
+
+    #[tokio::test]
+    async fn handle_cleanup_before_first_run_is_not_found() {
+        let (tx, _rx) = mpsc::channel(1);
+        let response = handle(&Method::GET, "/api/cleanup", &make_state(), &tx);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn handle_strikes_returns_retry_report() {
+        let (tx, _rx) = mpsc::channel(1);
+        let state = make_state();
+        state.record_run(
+            "retry",
+            make_run(None),
+            Some(TaskReport::Retry {
+                strikes: vec![],
+                verdicts: vec![],
+                actions: vec![],
+            }),
+        );
+        let response = handle(&Method::GET, "/api/retry/strikes", &state, &tx);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn handle_wrong_method_is_not_found() {
+        let (tx, _rx) = mpsc::channel(1);
+        let response = handle(&Method::DELETE, "/api/tasks", &make_state(), &tx);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,267 @@
+use std::fmt::Write;
+
+use time::{OffsetDateTime, UtcOffset, format_description::well_known::Rfc3339};
+
+use super::{ServerState, TaskRun};
+use crate::tasks::TaskReport;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.5em; }
+h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.3em 0.6em; border-bottom: 1px solid #ddd; vertical-align: top; }
+th { background: #f4f4f4; }
+.ok { color: #2e7d32; }
+.error { color: #c62828; }
+.empty { color: #777; font-style: italic; }
+ul { margin: 0; padding-left: 1.2em; }
+";
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_date(date: OffsetDateTime) -> String {
+    let date = match UtcOffset::current_local_offset() {
+        Ok(offset) => date.to_offset(offset),
+        Err(_) => date,
+    };
+    date.replace_nanosecond(0)
+        .unwrap_or(date)
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| date.to_string())
+        .replacen('T', " ", 1)
+}
+
+fn format_run(run: &TaskRun) -> String {
+    let duration = humantime::format_duration(
+        (run.finished_at - run.started_at)
+            .try_into()
+            .unwrap_or_default(),
+    );
+    match &run.error {
+        None => format!(
+            "<span class=\"ok\">{} (took {})</span>",
+            format_date(run.started_at),
+            duration
+        ),
+        Some(error) => format!(
+            "<span class=\"error\">{} failed: {}</span>",
+            format_date(run.started_at),
+            escape(error)
+        ),
+    }
+}
+
+fn render_services(html: &mut String, services: Vec<(super::Service, anyhow::Result<()>)>) {
+    html.push_str("<h2>Services</h2>");
+    if services.is_empty() {
+        html.push_str("<p class=\"empty\">No services configured</p>");
+        return;
+    }
+    html.push_str("<table><tr><th>Service</th><th>Host</th><th>Status</th></tr>");
+    for (service, result) in services {
+        let status = match result {
+            Ok(()) => "<span class=\"ok\">Reachable</span>".to_string(),
+            Err(e) => format!(
+                "<span class=\"error\">Unreachable: {}</span>",
+                escape(&format!("{e:#}"))
+            ),
+        };
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&service.name),
+            escape(&service.host),
+            status
+        );
+    }
+    html.push_str("</table>");
+}
+
+fn render_tasks(html: &mut String, state: &ServerState) {
+    html.push_str("<h2>Tasks</h2>");
+    let tasks = state.tasks();
+    if tasks.is_empty() {
+        html.push_str("<p class=\"empty\">No tasks configured</p>");
+        return;
+    }
+    html.push_str("<table><tr><th>Task</th><th>Next run</th><th>History</th></tr>");
+    for task in tasks {
+        let next_date = match task.next_date {
+            _ if task.running => "Running".to_string(),
+            Some(next_date) => format_date(next_date),
+            None => "Not scheduled".to_string(),
+        };
+        let history = state.history(&task.name);
+        let history = if history.is_empty() {
+            "<span class=\"empty\">Never run</span>".to_string()
+        } else {
+            let items: String = history
+                .iter()
+                .map(|run| format!("<li>{}</li>", format_run(run)))
+                .collect();
+            format!("<ul>{items}</ul>")
+        };
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&task.name),
+            next_date,
+            history
+        );
+    }
+    html.push_str("</table>");
+}
+
+fn render_protected_torrents(html: &mut String, state: &ServerState) {
+    html.push_str("<h2>Protected torrents</h2>");
+    let Some(TaskReport::Cleanup {
+        evaluated_at,
+        mut torrents,
+        ..
+    }) = state.report("cleanup")
+    else {
+        html.push_str("<p class=\"empty\">Cleanup has not run yet</p>");
+        return;
+    };
+
+    torrents.retain(|torrent| torrent.keep);
+    torrents.sort_by(|a, b| a.name.cmp(&b.name));
+    let _ = write!(html, "<p>Evaluated at {}</p>", format_date(evaluated_at));
+    if torrents.is_empty() {
+        html.push_str("<p class=\"empty\">No torrents are protected</p>");
+        return;
+    }
+    html.push_str("<table><tr><th>Torrent</th><th>Reasons</th></tr>");
+    for torrent in torrents {
+        let reasons: String = torrent
+            .messages
+            .iter()
+            .map(|message| format!("<li>{}</li>", escape(message)))
+            .collect();
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td><ul>{}</ul></td></tr>",
+            escape(&torrent.name),
+            reasons
+        );
+    }
+    html.push_str("</table>");
+}
+
+fn render_strikes(html: &mut String, state: &ServerState) {
+    html.push_str("<h2>Retry strikes</h2>");
+    let Some(TaskReport::Retry { strikes, .. }) = state.report("retry") else {
+        html.push_str("<p class=\"empty\">Retry has not run yet</p>");
+        return;
+    };
+    if strikes.is_empty() {
+        html.push_str("<p class=\"empty\">No pending strikes</p>");
+        return;
+    }
+    html.push_str("<table><tr><th>Download</th><th>Strikes</th><th>Last check</th></tr>");
+    for strike in strikes {
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}/{}</td><td>{}</td></tr>",
+            escape(&strike.download_id),
+            strike.strikes,
+            strike.max_strikes,
+            format_date(strike.last_check)
+        );
+    }
+    html.push_str("</table>");
+}
+
+/// Renders the read-only dashboard page.
+pub async fn render(state: &ServerState) -> String {
+    let services = state.check_services().await;
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+         <meta http-equiv=\"refresh\" content=\"60\">\
+         <title>arrmate</title><style>{STYLE}</style></head><body><h1>arrmate</h1>"
+    );
+    render_services(&mut html, services);
+    render_tasks(&mut html, state);
+    render_protected_torrents(&mut html, state);
+    render_strikes(&mut html, state);
+    html.push_str("</body></html>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::cleanup::TorrentEvaluation;
+
+    fn make_evaluation(name: &str, keep: bool, messages: Vec<&str>) -> TorrentEvaluation {
+        TorrentEvaluation {
+            name: name.to_string(),
+            hash: name.to_string(),
+            keep,
+            protected: keep,
+            seeding: false,
+            retained: false,
+            messages: messages.into_iter().map(str::to_owned).collect(),
+            expired: vec![],
+        }
+    }
+
+    #[test]
+    fn escape_replaces_html_characters() {
+        assert_eq!(
+            escape("<a href=\"x\">'&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[tokio::test]
+    async fn render_without_data_shows_placeholders() {
+        let html = render(&ServerState::default()).await;
+        assert!(html.contains("No services configured"));
+        assert!(html.contains("No tasks configured"));
+        assert!(html.contains("Cleanup has not run yet"));
+        assert!(html.contains("Retry has not run yet"));
+    }
+
+    #[tokio::test]
+    async fn render_lists_only_protected_torrents_with_reasons() {
+        let state = ServerState::default();
+        state.record_run(
+            "cleanup",
+            TaskRun {
+                started_at: OffsetDateTime::now_utc(),
+                finished_at: OffsetDateTime::now_utc(),
+                error: None,
+            },
+            Some(TaskReport::Cleanup {
+                evaluated_at: OffsetDateTime::now_utc(),
+                torrents: vec![
+                    make_evaluation("kept <1>", true, vec!["still in Sonarr queue"]),
+                    make_evaluation("removed", false, vec![]),
+                ],
+                actions: vec![],
+            }),
+        );
+        let html = render(&state).await;
+        assert!(html.contains("kept &lt;1&gt;"));
+        assert!(html.contains("still in Sonarr queue"));
+        assert!(!html.contains("<td>removed</td>"));
+    }
+}
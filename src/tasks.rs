@@ -1,12 +1,96 @@
 pub mod cleanup;
 pub mod retry;
 
-use anyhow::Result;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{Result, bail};
 use async_trait::async_trait;
+use serde::Serialize;
+
+/// Set once arrmate is shutting down. Running tasks check it before each
+/// destructive operation so they finish the one in flight and skip the rest.
+#[derive(Clone, Default, Debug)]
+pub struct ShutdownSignal(Arc<AtomicBool>);
+
+impl ShutdownSignal {
+    pub fn request(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Fails when shutdown was requested, naming the operation that is skipped.
+    pub fn check(&self, operation: &str) -> Result<()> {
+        if self.is_requested() {
+            bail!("Shutdown requested, skipping {operation}");
+        }
+        Ok(())
+    }
+}
+
+/// Something a task did to a single torrent or queue item.
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct ItemAction {
+    /// Torrent hash or download id of the item
+    pub id: String,
+    pub name: String,
+    pub action: String,
+    /// Why the task acted on the item, when it explains more than the action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Outcome of the last execution of a task, exposed through the HTTP API.
+#[derive(Clone, Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TaskReport {
+    Cleanup {
+        #[serde(with = "time::serde::rfc3339")]
+        evaluated_at: time::OffsetDateTime,
+        torrents: Vec<cleanup::TorrentEvaluation>,
+        actions: Vec<ItemAction>,
+    },
+    Retry {
+        strikes: Vec<retry::StrikeReport>,
+        verdicts: Vec<retry::QueueVerdict>,
+        actions: Vec<ItemAction>,
+    },
+}
+
+impl TaskReport {
+    /// Returns what the task did to each affected item.
+    pub fn actions(&self) -> &[ItemAction] {
+        match self {
+            TaskReport::Cleanup { actions, .. } | TaskReport::Retry { actions, .. } => actions,
+        }
+    }
+}
 
 #[async_trait]
 pub trait Task: Send + Sync {
     fn name(&self) -> &str;
     async fn execute(&mut self) -> Result<()>;
+    /// Executes the task as if `dry_run` was enabled, without changing anything.
+    async fn execute_dry_run(&mut self) -> Result<()>;
     fn next_date(&self, from: time::OffsetDateTime) -> Option<time::OffsetDateTime>;
+
+    /// Longest a single run of the task may take before it is cancelled.
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Whether the task should run as soon as arrmate starts.
+    fn run_on_start(&self) -> bool {
+        false
+    }
+
+    /// Returns what the last execution of the task found, if anything.
+    fn report(&self) -> Option<TaskReport> {
+        None
+    }
 }
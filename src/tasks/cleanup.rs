@@ -4,24 +4,29 @@ use std::{
     os::linux::fs::MetadataExt,
     path::Path,
     sync::Arc,
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use anyhow::{Context, Result, anyhow, bail};
 use async_trait::async_trait;
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
+use serde::Serialize;
 use time::OffsetDateTime;
-use tokio::fs;
+use tokio::{fs, sync::Semaphore, task::JoinSet};
 use url::Url;
 
 use crate::{
     apis::{
-        QBittorrentAPIInterface, SonarrAndRadarrAPIInterface,
+        QBittorrentAPIInterface, SonarrAndRadarrAPIInterface, UsenetAPIInterface,
         qbittorrent::Torrent,
-        types::{QueueResource, SystemStatus},
+        types::{QueueResource, SystemStatus, UsenetJob},
     },
-    config::{CategoriesConfig, CleanupConfig, TrackerConfig, TrackerIgnore},
-    tasks::Task,
+    config::{
+        CategoriesConfig, CleanupAction, CleanupConfig, EvictionOrder, Expression, Field,
+        FilterErrorPolicy, SizeRuleConfig, TagConfig, TrackerConfig, TrackerIgnore,
+        UsenetCleanupConfig, Value,
+    },
+    tasks::{ItemAction, ShutdownSignal, Task, TaskReport},
 };
 
 static VIDEO_EXTENSIONS: [&str; 38] = [
@@ -31,48 +36,114 @@ static VIDEO_EXTENSIONS: [&str; 38] = [
 ];
 
 struct TorrentFilterData {
-    ignored: bool,
+    /// Whether the torrent is kept for a reason that no other rule can
+    /// override, such as being in an *arr queue
+    protected: bool,
+    /// Whether the torrent is kept to seed on its tracker, which the tracker
+    /// seeding limits can override
+    seeding: bool,
+    /// Whether the torrent is kept because it has not reached a seeding
+    /// threshold yet, which expiry rules can override
+    retained: bool,
     messages: Vec<String>,
+    expired: Vec<String>,
 }
 
 impl TorrentFilterData {
     fn pass() -> Self {
         Self {
-            ignored: false,
+            protected: false,
+            seeding: false,
+            retained: false,
             messages: Vec::new(),
+            expired: Vec::new(),
+        }
+    }
+
+    fn kept(messages: Vec<String>) -> Self {
+        Self {
+            messages,
+            ..Self::pass()
         }
     }
 
     fn ignored(messages: Vec<String>) -> Self {
-        if messages.is_empty() {
-            Self::pass()
-        } else {
-            Self {
-                ignored: true,
-                messages,
-            }
+        let protected = !messages.is_empty();
+        Self {
+            protected,
+            ..Self::kept(messages)
         }
     }
 
     fn ignored_single_message(message: String) -> Self {
+        Self::ignored(vec![message])
+    }
+
+    /// Keeps the torrent seeding on its tracker.
+    fn seeding(messages: Vec<String>) -> Self {
+        let seeding = !messages.is_empty();
         Self {
-            ignored: true,
-            messages: vec![message],
+            seeding,
+            ..Self::kept(messages)
         }
     }
+
+    /// Keeps the torrent because it has not reached a seeding threshold yet.
+    fn retained(messages: Vec<String>) -> Self {
+        let retained = !messages.is_empty();
+        Self {
+            retained,
+            ..Self::kept(messages)
+        }
+    }
+
+    /// Marks the torrent for removal even if a seeding threshold retains it.
+    fn expired(message: String) -> Self {
+        Self {
+            expired: vec![message],
+            ..Self::pass()
+        }
+    }
+
+    /// Whether the torrent should be kept after all filters were applied.
+    fn keep(&self) -> bool {
+        self.protected || self.seeding || (self.retained && self.expired.is_empty())
+    }
+
+    /// Whether the torrent is only kept to seed and may be evicted to honor
+    /// the seeding limits of its tracker.
+    fn evictable(&self) -> bool {
+        self.seeding && !self.protected && !self.retained
+    }
 }
 
 impl AddAssign for TorrentFilterData {
     fn add_assign(&mut self, rhs: Self) {
-        self.ignored = self.ignored || rhs.ignored;
+        self.protected = self.protected || rhs.protected;
+        self.seeding = self.seeding || rhs.seeding;
+        self.retained = self.retained || rhs.retained;
         self.messages.extend(rhs.messages);
+        self.expired.extend(rhs.expired);
     }
 }
 
 #[async_trait]
-trait TorrentFilter: Send {
+trait TorrentFilter: Send + Sync {
     fn name(&self) -> String;
-    async fn filter(&mut self, torrent: &Torrent) -> Result<TorrentFilterData>;
+
+    /// Fetches whatever the filter needs once per run, before any torrent is
+    /// evaluated.
+    async fn prefetch(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData>;
+
+    /// What to do with a torrent when this filter fails to evaluate it.
+    /// Filters that depend on external services should keep the torrent.
+    fn error_policy(&self) -> FilterErrorPolicy {
+        FilterErrorPolicy::FailOpen
+    }
 }
 
 struct CategoriesFilter {
@@ -91,16 +162,15 @@ impl TorrentFilter for CategoriesFilter {
         "CategoriesFilter".to_string()
     }
 
-    async fn filter(&mut self, torrent: &Torrent) -> Result<TorrentFilterData> {
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
         let categories = match self.categories.as_ref() {
             Some(ignored_categories) => ignored_categories,
             None => return Ok(TorrentFilterData::pass()),
         };
 
-        if categories
-            .iter()
-            .any(|category| category.ignore && category.name == torrent.category)
-        {
+        if categories.iter().any(|category| {
+            category.name == torrent.category && category.action() == CleanupAction::Ignore
+        }) {
             return Ok(TorrentFilterData::ignored_single_message(format!(
                 "Ignoring torrent '{}' due to category '{}'",
                 torrent.name, torrent.category
@@ -111,16 +181,193 @@ impl TorrentFilter for CategoriesFilter {
     }
 }
 
+/// Keeps torrents carrying an ignored tag, and torrents without any opt-in
+/// tag when opt-in tags are configured.
+struct TagsFilter {
+    tags: Option<Vec<TagConfig>>,
+}
+
+impl TagsFilter {
+    fn new(tags: Option<Vec<TagConfig>>) -> Self {
+        Self { tags }
+    }
+}
+
+#[async_trait]
+impl TorrentFilter for TagsFilter {
+    fn name(&self) -> String {
+        "TagsFilter".to_string()
+    }
+
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        let tags = match self.tags.as_ref() {
+            Some(tags) => tags,
+            None => return Ok(TorrentFilterData::pass()),
+        };
+
+        let mut ignored_reasons: Vec<String> = tags
+            .iter()
+            .filter(|tag| tag.ignore && torrent.tags.contains(&tag.name))
+            .map(|tag| {
+                format!(
+                    "Ignoring torrent '{}' due to tag '{}'",
+                    torrent.name, tag.name
+                )
+            })
+            .collect();
+
+        let mut opt_in_tags = tags.iter().filter(|tag| tag.opt_in).peekable();
+        if opt_in_tags.peek().is_some() && !opt_in_tags.any(|tag| torrent.tags.contains(&tag.name))
+        {
+            ignored_reasons.push(format!(
+                "Ignoring torrent '{}' due to missing a tag that opts it into cleanup",
+                torrent.name
+            ));
+        }
+
+        Ok(TorrentFilterData::ignored(ignored_reasons))
+    }
+}
+
+/// Whether `url` is one of the entries qBittorrent lists for DHT, PeX and LSD
+/// rather than a real tracker.
+fn is_pseudo_tracker(url: &str) -> bool {
+    url.starts_with("** [")
+}
+
+/// Returns the announce URLs of the real trackers of `torrent`.
+fn announce_urls(torrent: &Torrent) -> Vec<Url> {
+    torrent
+        .trackers
+        .iter()
+        .filter(|t| !is_pseudo_tracker(&t.url))
+        .filter_map(|t| Url::parse(&t.url).ok())
+        .collect()
+}
+
+/// Returns `url` without its path, query and credentials so that passkeys
+/// never end up in the logs.
+fn redacted_url(url: &Url) -> String {
+    match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}://{host}:{port}", url.scheme()),
+        (Some(host), None) => format!("{}://{host}", url.scheme()),
+        _ => format!("{}://", url.scheme()),
+    }
+}
+
+/// Returns the configured trackers that `torrent` announces to.
+fn matching_trackers<'a>(
+    trackers: &'a [TrackerConfig],
+    torrent: &Torrent,
+) -> Vec<&'a TrackerConfig> {
+    let torrent_tracker_urls = announce_urls(torrent);
+
+    trackers
+        .iter()
+        .filter(|tracker| {
+            torrent_tracker_urls.iter().any(|url| {
+                let matched = tracker.matches(url);
+                if matched {
+                    trace!(
+                        "Torrent '{}' matched tracker '{}' through {}",
+                        torrent.name,
+                        tracker.name,
+                        redacted_url(url)
+                    );
+                }
+                matched
+            })
+        })
+        .collect()
+}
+
+/// Returns the reason to keep `torrent` when it has not reached the `ratio`
+/// and `seeding_time` thresholds configured by `source` (e.g. "tracker 'x'"),
+/// or `None` when the thresholds allow it to be removed.
+fn seeding_threshold_reason(
+    torrent: &Torrent,
+    source: &str,
+    ratio: Option<f64>,
+    seeding_time: Option<Duration>,
+    require_both: bool,
+) -> Option<String> {
+    let ratio_reached_opt = ratio.map(|ratio| torrent.ratio >= ratio);
+
+    let seeding_time_reached_opt =
+        seeding_time.map(|seeding_time| torrent.seeding_time >= seeding_time);
+
+    if let Some(ratio_reached) = ratio_reached_opt
+        && let Some(seeding_time_reached) = seeding_time_reached_opt
+    {
+        if require_both && (!ratio_reached || !seeding_time_reached) {
+            Some(format!(
+                "Ignoring torrent '{}' due to ratio {:.2} or seeding time {} not reaching minimum required ratio {:.2} or time {} for {}",
+                torrent.name,
+                torrent.ratio,
+                humantime::format_duration(torrent.seeding_time),
+                ratio.unwrap_or(0.0),
+                humantime::format_duration(seeding_time.unwrap_or(Duration::from_secs(0))),
+                source
+            ))
+        } else if !ratio_reached && !seeding_time_reached {
+            Some(format!(
+                "Ignoring torrent '{}' due to ratio {:.2} and seeding time {} not reaching minimum required ratio {:.2} and time {} for {}",
+                torrent.name,
+                torrent.ratio,
+                humantime::format_duration(torrent.seeding_time),
+                ratio.unwrap_or(0.0),
+                humantime::format_duration(seeding_time.unwrap_or(Duration::from_secs(0))),
+                source
+            ))
+        } else {
+            None
+        }
+    } else if ratio_reached_opt.is_some_and(|ratio_reached| !ratio_reached) {
+        Some(format!(
+            "Ignoring torrent '{}' due to ratio {:.2} not reaching minimum required ratio {:.2} for {}",
+            torrent.name,
+            torrent.ratio,
+            ratio.unwrap_or(0.0),
+            source
+        ))
+    } else if seeding_time_reached_opt.is_some_and(|seeding_time_reached| !seeding_time_reached) {
+        Some(format!(
+            "Ignoring torrent '{}' due to seeding time {} not reaching minimum required time {} for {}",
+            torrent.name,
+            humantime::format_duration(torrent.seeding_time),
+            humantime::format_duration(seeding_time.unwrap_or(Duration::from_secs(0))),
+            source
+        ))
+    } else {
+        None
+    }
+}
+
+/// Applies tracker protections and seeding thresholds.
+///
+/// Thresholds are resolved most specific first: tags that define a `ratio`
+/// or `seeding_time` replace the thresholds of the category, which in turn
+/// replace the thresholds of the trackers the torrent belongs to. The global
+/// ratio is only used when no tracker, category or tag rule matched.
 struct TrackerFilter {
     global_ratio: Option<f64>,
     trackers: Option<Vec<TrackerConfig>>,
+    categories: Option<Vec<CategoriesConfig>>,
+    tags: Option<Vec<TagConfig>>,
 }
 
 impl TrackerFilter {
-    fn new(global_ratio: Option<f64>, trackers: Option<Vec<TrackerConfig>>) -> Self {
+    fn new(
+        global_ratio: Option<f64>,
+        trackers: Option<Vec<TrackerConfig>>,
+        categories: Option<Vec<CategoriesConfig>>,
+        tags: Option<Vec<TagConfig>>,
+    ) -> Self {
         Self {
             global_ratio,
             trackers,
+            categories,
+            tags,
         }
     }
 }
@@ -131,35 +378,41 @@ impl TorrentFilter for TrackerFilter {
         "TrackerFilter".to_string()
     }
 
-    async fn filter(&mut self, torrent: &Torrent) -> Result<TorrentFilterData> {
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        let tag_rules: Vec<&TagConfig> = self
+            .tags
+            .iter()
+            .flatten()
+            .filter(|tag| tag.has_thresholds() && torrent.tags.contains(&tag.name))
+            .collect();
+
+        let category_rule = self
+            .categories
+            .iter()
+            .flatten()
+            .find(|category| category.name == torrent.category && category.has_thresholds())
+            .filter(|_| tag_rules.is_empty());
+
+        let has_specific_rule = category_rule.is_some() || !tag_rules.is_empty();
+
         let trackers = match self.trackers.as_ref() {
-            Some(ignored_trackers) => ignored_trackers,
+            Some(ignored_trackers) => ignored_trackers.as_slice(),
+            None if has_specific_rule => &[],
             None => return Ok(TorrentFilterData::pass()),
         };
 
         let mut ignored_reasons = vec![];
+        let mut linked_reasons = vec![];
+        let mut retained_reasons = vec![];
 
-        let torrent_tracker_urls: Vec<Url> = torrent
-            .trackers
-            .iter()
-            .filter_map(|t| Url::parse(&t.url).ok())
-            .collect();
-
-        let configured_trackers: Vec<&TrackerConfig> = trackers
-            .iter()
-            .filter(|tracker| {
-                torrent_tracker_urls.iter().any(|url| {
-                    url.domain()
-                        .is_some_and(|v| tracker.domain.contains(&v.to_string()))
-                })
-            })
-            .collect();
+        let configured_trackers = matching_trackers(trackers, torrent);
 
         if configured_trackers.is_empty()
+            && !has_specific_rule
             && let Some(global_ratio) = self.global_ratio
             && torrent.ratio < global_ratio
         {
-            ignored_reasons.push(format!(
+            retained_reasons.push(format!(
                     "Ignoring torrent '{}' due to ratio {:.2} not reaching minimum required global ratio {:.2}",
                     torrent.name, torrent.ratio, global_ratio
                 ));
@@ -223,7 +476,7 @@ impl TorrentFilter for TrackerFilter {
                     if let Some(percentage) = percentage_multiple_linked
                         && percentage >= tracker.hard_links_percentage as f64
                     {
-                        ignored_reasons.push(format!(
+                        linked_reasons.push(format!(
                                 "Ignoring torrent '{}' due to tracker '{}' with {:.0}% multiple hard linked files",
                                 torrent.name, tracker.name, percentage
                             ));
@@ -232,114 +485,258 @@ impl TorrentFilter for TrackerFilter {
                 TrackerIgnore::Never => {}
             }
 
-            let ratio_reached_opt = tracker.ratio.map(|ratio| torrent.ratio >= ratio);
+            if has_specific_rule {
+                continue;
+            }
 
-            let seeding_time_reached_opt = tracker
-                .seeding_time
-                .map(|seeding_time| torrent.seeding_time >= seeding_time);
+            if let Some(reason) = seeding_threshold_reason(
+                torrent,
+                &format!("tracker '{}'", tracker.name),
+                tracker.ratio,
+                tracker.seeding_time,
+                tracker.require_both,
+            ) {
+                retained_reasons.push(reason);
+            }
+        }
 
-            if let Some(ratio_reached) = ratio_reached_opt
-                && let Some(seeding_time_reached) = seeding_time_reached_opt
-            {
-                if tracker.require_both && (!ratio_reached || !seeding_time_reached) {
-                    ignored_reasons.push(format!(
-                        "Ignoring torrent '{}' due to ratio {:.2} or seeding time {} not reaching minimum required ratio {:.2} or time {} for tracker '{}'",
-                        torrent.name,
-                        torrent.ratio,
-                        humantime::format_duration(torrent.seeding_time),
-                        tracker.ratio.unwrap_or(0.0),
-                        humantime::format_duration(
-                            tracker.seeding_time.unwrap_or(Duration::from_secs(0))
-                        ),
-                        tracker.name
-                    ));
-                } else if !ratio_reached && !seeding_time_reached {
-                    ignored_reasons.push(format!(
-                        "Ignoring torrent '{}' due to ratio {:.2} and seeding time {} not reaching minimum required ratio {:.2} and time {} for tracker '{}'",
-                        torrent.name,
-                        torrent.ratio,
-                        humantime::format_duration(torrent.seeding_time),
-                        tracker.ratio.unwrap_or(0.0),
-                        humantime::format_duration(
-                            tracker.seeding_time.unwrap_or(Duration::from_secs(0))
-                        ),
-                        tracker.name
-                    ));
-                }
-            } else if ratio_reached_opt.is_some_and(|ratio_reached| !ratio_reached) {
-                ignored_reasons.push(format!(
-                    "Ignoring torrent '{}' due to ratio {:.2} not reaching minimum required ratio {:.2} for tracker '{}'",
-                    torrent.name,
-                    torrent.ratio,
-                    tracker.ratio.unwrap_or(0.0),
-                    tracker.name
-                ));
-            } else if seeding_time_reached_opt
-                .is_some_and(|seeding_time_reached| !seeding_time_reached)
-            {
-                ignored_reasons.push(format!(
-                    "Ignoring torrent '{}' due to seeding time {} not reaching minimum required time {} for tracker '{}'",
-                    torrent.name,
-                    humantime::format_duration(torrent.seeding_time),
-                    humantime::format_duration(
-                        tracker.seeding_time.unwrap_or(Duration::from_secs(0))
-                    ),
-                    tracker.name
-                ));
+        if let Some(category) = category_rule
+            && let Some(reason) = seeding_threshold_reason(
+                torrent,
+                &format!("category '{}'", category.name),
+                category.ratio,
+                category.seeding_time,
+                category.require_both,
+            )
+        {
+            retained_reasons.push(reason);
+        }
+
+        for tag in tag_rules {
+            if let Some(reason) = seeding_threshold_reason(
+                torrent,
+                &format!("tag '{}'", tag.name),
+                tag.ratio,
+                tag.seeding_time,
+                tag.require_both,
+            ) {
+                retained_reasons.push(reason);
             }
         }
 
-        Ok(TorrentFilterData::ignored(ignored_reasons))
+        // Only hard-linked torrents may be evicted by the seeding limits
+        let mut data = TorrentFilterData::ignored(ignored_reasons);
+        data += TorrentFilterData::seeding(linked_reasons);
+        data += TorrentFilterData::retained(retained_reasons);
+        Ok(data)
+    }
+}
+
+/// Returns how long ago `moment` happened, or zero when it is in the future.
+fn elapsed_since(moment: OffsetDateTime, now: OffsetDateTime) -> Duration {
+    (now - moment).try_into().unwrap_or_default()
+}
+
+/// Resolves the value of an expression `field` for `torrent`.
+fn torrent_field(torrent: &Torrent, field: Field, now: OffsetDateTime) -> Option<Value> {
+    match field {
+        Field::Size => Some(Value::Number(torrent.total_size as f64)),
+        Field::Ratio => Some(Value::Number(torrent.ratio)),
+        Field::SeedingTime => Some(Value::Duration(torrent.seeding_time)),
+        Field::Category => Some(Value::Text(torrent.category.clone())),
+        Field::Tags => Some(Value::List(torrent.tags.clone())),
+        Field::LastActivity => torrent
+            .last_activity
+            .map(|moment| Value::Duration(elapsed_since(moment, now))),
+        Field::AddedOn => torrent
+            .added_on
+            .map(|moment| Value::Duration(elapsed_since(moment, now))),
+        Field::Tracker => Some(Value::List(
+            announce_urls(torrent)
+                .iter()
+                .filter_map(|url| url.host_str().map(str::to_owned))
+                .collect(),
+        )),
+    }
+}
+
+/// Keeps every torrent for which the configured cleanup condition is false.
+struct ConditionFilter {
+    condition: Option<Expression>,
+}
+
+impl ConditionFilter {
+    fn new(condition: Option<Expression>) -> Self {
+        Self { condition }
+    }
+}
+
+#[async_trait]
+impl TorrentFilter for ConditionFilter {
+    fn name(&self) -> String {
+        "ConditionFilter".to_string()
+    }
+
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        let condition = match self.condition.as_ref() {
+            Some(condition) => condition,
+            None => return Ok(TorrentFilterData::pass()),
+        };
+
+        let now = OffsetDateTime::now_utc();
+        if condition.evaluate(&|field| torrent_field(torrent, field, now)) {
+            Ok(TorrentFilterData::pass())
+        } else {
+            // Protected, so that expiry rules can't remove it either
+            Ok(TorrentFilterData::ignored_single_message(format!(
+                "Ignoring torrent '{}' due to cleanup condition '{}' not matching",
+                torrent.name, condition
+            )))
+        }
+    }
+}
+
+/// Forces the removal of torrents that have been inactive for too long or
+/// that are too large for how little they seed, even when they have not
+/// reached their seeding thresholds. Category rules take precedence over the
+/// rules of the trackers the torrent belongs to.
+struct ExpiryFilter {
+    trackers: Option<Vec<TrackerConfig>>,
+    categories: Option<Vec<CategoriesConfig>>,
+}
+
+impl ExpiryFilter {
+    fn new(
+        trackers: Option<Vec<TrackerConfig>>,
+        categories: Option<Vec<CategoriesConfig>>,
+    ) -> Self {
+        Self {
+            trackers,
+            categories,
+        }
+    }
+
+    fn expiry_reason(
+        torrent: &Torrent,
+        source: &str,
+        inactive_for: Option<Duration>,
+        size_rule: Option<&SizeRuleConfig>,
+        now: OffsetDateTime,
+    ) -> Option<String> {
+        if let Some(inactive_for) = inactive_for
+            && let Some(last_activity) = torrent.last_activity
+            && elapsed_since(last_activity, now) >= inactive_for
+        {
+            return Some(format!(
+                "Removing torrent '{}' due to no activity for {} for {}",
+                torrent.name,
+                humantime::format_duration(inactive_for),
+                source
+            ));
+        }
+
+        if let Some(rule) = size_rule
+            && torrent.total_size >= 0
+            && torrent.total_size as u64 > rule.larger_than.0
+            && rule.ratio_below.is_none_or(|ratio| torrent.ratio < ratio)
+            && rule
+                .seeding_time
+                .is_none_or(|seeding_time| torrent.seeding_time >= seeding_time)
+        {
+            return Some(format!(
+                "Removing torrent '{}' due to size {} bytes with ratio {:.2} after seeding {} for {}",
+                torrent.name,
+                torrent.total_size,
+                torrent.ratio,
+                humantime::format_duration(torrent.seeding_time),
+                source
+            ));
+        }
+
+        None
+    }
+}
+
+#[async_trait]
+impl TorrentFilter for ExpiryFilter {
+    fn name(&self) -> String {
+        "ExpiryFilter".to_string()
+    }
+
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        let now = OffsetDateTime::now_utc();
+
+        let category_rule = self
+            .categories
+            .iter()
+            .flatten()
+            .find(|category| category.name == torrent.category && category.has_expiry_rules());
+
+        let reason = match category_rule {
+            Some(category) => Self::expiry_reason(
+                torrent,
+                &format!("category '{}'", category.name),
+                category.inactive_for,
+                category.size_rule.as_ref(),
+                now,
+            ),
+            None => matching_trackers(self.trackers.as_deref().unwrap_or_default(), torrent)
+                .into_iter()
+                .find_map(|tracker| {
+                    Self::expiry_reason(
+                        torrent,
+                        &format!("tracker '{}'", tracker.name),
+                        tracker.inactive_for,
+                        tracker.size_rule.as_ref(),
+                        now,
+                    )
+                }),
+        };
+
+        Ok(reason.map_or_else(TorrentFilterData::pass, TorrentFilterData::expired))
     }
 }
 
+/// Returns the lowercase download ids of the items in an *arr queue.
+fn queue_download_ids(queue_items: Vec<QueueResource>) -> HashSet<String> {
+    queue_items
+        .into_iter()
+        .filter_map(|v| v.download_id)
+        .map(|id| id.to_lowercase())
+        .collect()
+}
+
 struct SonarrFilter {
     sonarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
-    cached_queue: Option<(Instant, Vec<QueueResource>)>,
-    cached_system_status: Option<(Instant, SystemStatus)>,
+    queue_download_ids: Option<HashSet<String>>,
 }
 
 impl SonarrFilter {
     fn new(sonarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>) -> Self {
         Self {
             sonarr,
-            cached_queue: None,
-            cached_system_status: None,
+            queue_download_ids: None,
         }
     }
 
-    async fn get_queue(&mut self) -> Result<Vec<QueueResource>> {
-        if let Some((fetched_at, ref queue)) = self.cached_queue {
-            if fetched_at.elapsed() < Duration::from_secs(60) {
-                return Ok(queue.clone());
-            }
-        }
-        let queue = match &self.sonarr {
+    async fn get_queue(&self) -> Result<Vec<QueueResource>> {
+        match &self.sonarr {
             Some(api) => api
                 .get_queue()
                 .await
-                .context("Could not retrieve Sonarr queue")?,
+                .context("Could not retrieve Sonarr queue"),
             None => bail!("No Sonarr API available"),
-        };
-        self.cached_queue = Some((Instant::now(), queue.clone()));
-        Ok(queue)
+        }
     }
 
-    async fn get_system_status(&mut self) -> Result<SystemStatus> {
-        if let Some((fetched_at, ref status)) = self.cached_system_status {
-            if fetched_at.elapsed() < Duration::from_secs(60) {
-                return Ok(status.clone());
-            }
-        }
-        let status = match &self.sonarr {
+    async fn get_system_status(&self) -> Result<SystemStatus> {
+        match &self.sonarr {
             Some(api) => api
                 .get_system_status()
                 .await
-                .context("Could not retrieve Sonarr system status")?,
+                .context("Could not retrieve Sonarr system status"),
             None => bail!("No Sonarr API available"),
-        };
-        self.cached_system_status = Some((Instant::now(), status.clone()));
-        Ok(status)
+        }
     }
 }
 
@@ -349,13 +746,16 @@ impl TorrentFilter for SonarrFilter {
         "SonarrFilter".to_string()
     }
 
-    async fn filter(&mut self, torrent: &Torrent) -> Result<TorrentFilterData> {
+    fn error_policy(&self) -> FilterErrorPolicy {
+        FilterErrorPolicy::FailClosed
+    }
+
+    async fn prefetch(&mut self) -> Result<()> {
         if self.sonarr.is_none() {
-            return Ok(TorrentFilterData::pass());
+            return Ok(());
         }
 
         let queue_items = self.get_queue().await?;
-        // trace!("Sonarr Queue: {}", queue_items.len());
 
         // Ignore cleanup if Sonarr has started recently
         if queue_items.is_empty() {
@@ -367,11 +767,18 @@ impl TorrentFilter for SonarrFilter {
             }
         }
 
-        let queue_download_ids = queue_items
-            .into_iter()
-            .filter_map(|v| v.download_id)
-            .map(|id| id.to_lowercase())
-            .collect::<HashSet<String>>();
+        self.queue_download_ids = Some(queue_download_ids(queue_items));
+        Ok(())
+    }
+
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        if self.sonarr.is_none() {
+            return Ok(TorrentFilterData::pass());
+        }
+
+        let Some(queue_download_ids) = &self.queue_download_ids else {
+            bail!("Sonarr queue was not fetched");
+        };
 
         if queue_download_ids.contains(&torrent.hash.to_lowercase()) {
             return Ok(TorrentFilterData::ignored_single_message(format!(
@@ -386,51 +793,35 @@ impl TorrentFilter for SonarrFilter {
 
 struct RadarrFilter {
     radarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
-    cached_queue: Option<(Instant, Vec<QueueResource>)>,
-    cached_system_status: Option<(Instant, SystemStatus)>,
+    queue_download_ids: Option<HashSet<String>>,
 }
 
 impl RadarrFilter {
     fn new(radarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>) -> Self {
         Self {
             radarr,
-            cached_queue: None,
-            cached_system_status: None,
+            queue_download_ids: None,
         }
     }
 
-    async fn get_queue(&mut self) -> Result<Vec<QueueResource>> {
-        if let Some((fetched_at, ref queue)) = self.cached_queue {
-            if fetched_at.elapsed() < Duration::from_secs(60) {
-                return Ok(queue.clone());
-            }
-        }
-        let queue = match &self.radarr {
+    async fn get_queue(&self) -> Result<Vec<QueueResource>> {
+        match &self.radarr {
             Some(api) => api
                 .get_queue()
                 .await
-                .context("Could not retrieve Radarr queue")?,
+                .context("Could not retrieve Radarr queue"),
             None => bail!("No Radarr API available"),
-        };
-        self.cached_queue = Some((Instant::now(), queue.clone()));
-        Ok(queue)
+        }
     }
 
-    async fn get_system_status(&mut self) -> Result<SystemStatus> {
-        if let Some((fetched_at, ref status)) = self.cached_system_status {
-            if fetched_at.elapsed() < Duration::from_secs(60) {
-                return Ok(status.clone());
-            }
-        }
-        let status = match &self.radarr {
+    async fn get_system_status(&self) -> Result<SystemStatus> {
+        match &self.radarr {
             Some(api) => api
                 .get_system_status()
                 .await
-                .context("Could not retrieve Radarr system status")?,
+                .context("Could not retrieve Radarr system status"),
             None => bail!("No Radarr API available"),
-        };
-        self.cached_system_status = Some((Instant::now(), status.clone()));
-        Ok(status)
+        }
     }
 }
 
@@ -440,9 +831,13 @@ impl TorrentFilter for RadarrFilter {
         "RadarrFilter".to_string()
     }
 
-    async fn filter(&mut self, torrent: &Torrent) -> Result<TorrentFilterData> {
+    fn error_policy(&self) -> FilterErrorPolicy {
+        FilterErrorPolicy::FailClosed
+    }
+
+    async fn prefetch(&mut self) -> Result<()> {
         if self.radarr.is_none() {
-            return Ok(TorrentFilterData::pass());
+            return Ok(());
         }
 
         let queue_items = self.get_queue().await?;
@@ -457,11 +852,18 @@ impl TorrentFilter for RadarrFilter {
             }
         }
 
-        let queue_download_ids = queue_items
-            .into_iter()
-            .filter_map(|v| v.download_id)
-            .map(|id| id.to_lowercase())
-            .collect::<HashSet<String>>();
+        self.queue_download_ids = Some(queue_download_ids(queue_items));
+        Ok(())
+    }
+
+    async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        if self.radarr.is_none() {
+            return Ok(TorrentFilterData::pass());
+        }
+
+        let Some(queue_download_ids) = &self.queue_download_ids else {
+            bail!("Radarr queue was not fetched");
+        };
 
         if queue_download_ids.contains(&torrent.hash.to_lowercase()) {
             return Ok(TorrentFilterData::ignored_single_message(format!(
@@ -474,11 +876,173 @@ impl TorrentFilter for RadarrFilter {
     }
 }
 
+/// Removes torrents that are only kept to seed on a tracker once that tracker
+/// exceeds its `max_torrents` or `max_total_size`, lowest value first.
+/// Torrents that are protected or that have not reached their seeding
+/// thresholds still count towards the limits but are never removed.
+fn apply_seeding_limits(
+    trackers: &[TrackerConfig],
+    processed_torrents: &mut HashMap<Torrent, TorrentFilterData>,
+) {
+    for tracker in trackers {
+        if tracker.max_torrents.is_none() && tracker.max_total_size.is_none() {
+            continue;
+        }
+
+        let on_tracker = |torrent: &Torrent| {
+            !matching_trackers(std::slice::from_ref(tracker), torrent).is_empty()
+        };
+
+        let kept: Vec<&Torrent> = processed_torrents
+            .iter()
+            .filter(|(torrent, data)| data.keep() && on_tracker(torrent))
+            .map(|(torrent, _)| torrent)
+            .collect();
+        let mut count = kept.len();
+        let mut total_size: u64 = kept.iter().map(|t| t.total_size.max(0) as u64).sum();
+
+        let mut candidates: Vec<Torrent> = kept
+            .into_iter()
+            .filter(|torrent| processed_torrents[*torrent].evictable())
+            .cloned()
+            .collect();
+        match tracker.evict.clone().unwrap_or_default() {
+            EvictionOrder::LowestRatio => {
+                candidates.sort_by(|a, b| a.ratio.total_cmp(&b.ratio));
+            }
+            EvictionOrder::Oldest => {
+                candidates.sort_by_key(|t| t.added_on);
+            }
+        }
+
+        for torrent in candidates {
+            let over_count = tracker.max_torrents.is_some_and(|max| count > max);
+            let over_size = tracker.max_total_size.is_some_and(|max| total_size > max.0);
+            if !over_count && !over_size {
+                break;
+            }
+
+            count -= 1;
+            total_size = total_size.saturating_sub(torrent.total_size.max(0) as u64);
+            if let Some(data) = processed_torrents.get_mut(&torrent) {
+                data.seeding = false;
+                data.expired.push(format!(
+                    "Removing torrent '{}' due to tracker '{}' exceeding its seeding limits",
+                    torrent.name, tracker.name
+                ));
+            }
+        }
+    }
+}
+
+/// Maximum number of torrents a filter evaluates at the same time.
+const MAX_CONCURRENT_EVALUATIONS: usize = 32;
+
+/// Evaluates `filter` for every torrent, a bounded number at a time, and
+/// returns the results in the same order as `torrents`.
+async fn evaluate_concurrently(
+    filter: &Arc<dyn TorrentFilter>,
+    torrents: &[Arc<Torrent>],
+) -> Vec<Result<TorrentFilterData>> {
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_EVALUATIONS));
+    let mut set = JoinSet::new();
+    for (index, torrent) in torrents.iter().enumerate() {
+        let filter = filter.clone();
+        let torrent = torrent.clone();
+        let semaphore = semaphore.clone();
+        set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            trace!("Evaluating torrent '{}'", torrent.name);
+            (index, filter.filter(&torrent).await)
+        });
+    }
+
+    let mut results: Vec<Result<TorrentFilterData>> = torrents
+        .iter()
+        .map(|_| Err(anyhow!("Filter evaluation did not complete")))
+        .collect();
+    while let Some(join_result) = set.join_next().await {
+        match join_result {
+            Ok((index, result)) => results[index] = result,
+            Err(e) => error!("Filter evaluation task panicked: {e}"),
+        }
+    }
+    results
+}
+
+/// Applies the error `policy` of `filter` to a torrent it failed to evaluate.
+fn handle_filter_error(
+    policy: FilterErrorPolicy,
+    filter: &dyn TorrentFilter,
+    torrent: &Torrent,
+    error: &anyhow::Error,
+    filter_data: &mut TorrentFilterData,
+) -> Result<()> {
+    match policy {
+        FilterErrorPolicy::FailOpen => error!(
+            "Filter '{}' failed for torrent '{}', ignoring: {error}",
+            filter.name(),
+            torrent.name
+        ),
+        FilterErrorPolicy::FailClosed => {
+            error!(
+                "Filter '{}' failed for torrent '{}', keeping it: {error}",
+                filter.name(),
+                torrent.name
+            );
+            *filter_data += TorrentFilterData::ignored_single_message(format!(
+                "Ignoring torrent '{}' due to filter '{}' failing: {error}",
+                torrent.name,
+                filter.name()
+            ));
+        }
+        FilterErrorPolicy::Abort => bail!(
+            "Filter '{}' failed for torrent '{}', aborting cleanup: {error}",
+            filter.name(),
+            torrent.name
+        ),
+    }
+    Ok(())
+}
+
+/// Verdict of the last cleanup run for a single torrent.
+#[derive(Clone, Serialize, Debug)]
+pub struct TorrentEvaluation {
+    pub name: String,
+    pub hash: String,
+    pub keep: bool,
+    pub protected: bool,
+    pub seeding: bool,
+    pub retained: bool,
+    pub messages: Vec<String>,
+    pub expired: Vec<String>,
+}
+
+impl TorrentEvaluation {
+    fn new(torrent: &Torrent, data: &TorrentFilterData) -> Self {
+        Self {
+            name: torrent.name.clone(),
+            hash: torrent.hash.clone(),
+            keep: data.keep(),
+            protected: data.protected,
+            seeding: data.seeding,
+            retained: data.retained,
+            messages: data.messages.clone(),
+            expired: data.expired.clone(),
+        }
+    }
+}
+
 pub struct CleanupController {
     cleanup_config: CleanupConfig,
-    qbittorrent: Arc<dyn QBittorrentAPIInterface>,
+    /// Torrents are only cleaned with qBittorrent, usenet jobs without it
+    qbittorrent: Option<Arc<dyn QBittorrentAPIInterface>>,
     sonarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
     radarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
+    usenet: Vec<Arc<dyn UsenetAPIInterface>>,
+    shutdown: ShutdownSignal,
+    last_evaluation: Option<(OffsetDateTime, Vec<TorrentEvaluation>)>,
+    last_actions: Vec<ItemAction>,
 }
 
 impl CleanupController {
@@ -487,18 +1051,40 @@ impl CleanupController {
         qbittorrent: Option<Arc<dyn QBittorrentAPIInterface>>,
         sonarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
         radarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
+        usenet: Vec<Arc<dyn UsenetAPIInterface>>,
+        shutdown: ShutdownSignal,
     ) -> Result<Self> {
-        qbittorrent
-            .map(|qbittorrent| Self {
-                cleanup_config,
-                qbittorrent,
-                sonarr,
-                radarr,
-            })
-            .context("Could not initialize cleanup task")
+        let cleans_usenet = cleanup_config.usenet.is_some() && !usenet.is_empty();
+        if qbittorrent.is_none() && !cleans_usenet {
+            bail!("Could not initialize cleanup task without qBittorrent or usenet clients");
+        }
+        Ok(Self {
+            cleanup_config,
+            qbittorrent,
+            sonarr,
+            radarr,
+            usenet,
+            shutdown,
+            last_evaluation: None,
+            last_actions: Vec::new(),
+        })
+    }
+
+    /// Returns the action configured for the category of `torrent`.
+    fn action_for(&self, torrent: &Torrent) -> CleanupAction {
+        self.cleanup_config
+            .categories
+            .iter()
+            .flatten()
+            .find(|category| category.name == torrent.category)
+            .map(|category| category.action())
+            .unwrap_or_default()
     }
 
     async fn delete_torrents(&self, torrents: Vec<&Torrent>) -> Result<usize> {
+        let Some(qbittorrent) = &self.qbittorrent else {
+            return Ok(0);
+        };
         if torrents.is_empty() {
             return Ok(0);
         }
@@ -507,10 +1093,22 @@ impl CleanupController {
             return Ok(0);
         }
 
-        let torrents_size: usize = torrents.len();
-        self.qbittorrent
-            .delete_torrents(torrents, Some(true))
-            .await?;
+        let (with_files, without_files): (Vec<&Torrent>, Vec<&Torrent>) = torrents
+            .into_iter()
+            .filter(|torrent| self.action_for(torrent) != CleanupAction::Ignore)
+            .partition(|torrent| self.action_for(torrent) == CleanupAction::Delete);
+
+        let torrents_size: usize = with_files.len() + without_files.len();
+        if !with_files.is_empty() {
+            self.shutdown.check("deleting torrents")?;
+            qbittorrent.delete_torrents(with_files, Some(true)).await?;
+        }
+        if !without_files.is_empty() {
+            self.shutdown.check("removing torrents")?;
+            qbittorrent
+                .delete_torrents(without_files, Some(false))
+                .await?;
+        }
 
         Ok(torrents_size)
     }
@@ -518,55 +1116,103 @@ impl CleanupController {
     async fn process_with_filters(
         &self,
         torrents: Vec<Torrent>,
-        mut filters: Vec<Box<dyn TorrentFilter>>,
+        filters: Vec<Box<dyn TorrentFilter>>,
     ) -> Result<HashMap<Torrent, TorrentFilterData>> {
-        let mut processed_torrents = HashMap::new();
-
-        for torrent in torrents {
-            processed_torrents.insert(torrent, TorrentFilterData::pass());
-        }
+        let torrents: Vec<Arc<Torrent>> = torrents.into_iter().map(Arc::new).collect();
+        let mut torrents_data: Vec<TorrentFilterData> =
+            torrents.iter().map(|_| TorrentFilterData::pass()).collect();
 
-        for filter in &mut filters {
+        for mut filter in filters {
             debug!("Applying filter {}", filter.name());
-            for (torrent, filter_data) in &mut processed_torrents {
-                debug!("Evaluating torrent '{}'", torrent.name);
-                if let Ok(data) = filter.filter(&torrent).await {
-                    *filter_data += data;
-                } else {
-                    error!(
-                        "Filter '{}' failed for torrent '{}', ignoring",
-                        filter.name(),
-                        torrent.name
-                    );
+            let policy = self.cleanup_config.on_filter_error.map_or_else(
+                || filter.error_policy(),
+                |policy| policy.max(filter.error_policy()),
+            );
+
+            if let Err(e) = filter.prefetch().await {
+                for (torrent, filter_data) in torrents.iter().zip(&mut torrents_data) {
+                    handle_filter_error(policy, filter.as_ref(), torrent, &e, filter_data)?;
+                }
+                continue;
+            }
+
+            let filter: Arc<dyn TorrentFilter> = Arc::from(filter);
+            let results = evaluate_concurrently(&filter, &torrents).await;
+            for ((torrent, filter_data), result) in
+                torrents.iter().zip(&mut torrents_data).zip(results)
+            {
+                match result {
+                    Ok(data) => *filter_data += data,
+                    Err(e) => {
+                        handle_filter_error(policy, filter.as_ref(), torrent, &e, filter_data)?
+                    }
                 }
             }
         }
 
-        return Ok(processed_torrents);
+        Ok(torrents
+            .into_iter()
+            .map(Arc::unwrap_or_clone)
+            .zip(torrents_data)
+            .collect())
     }
 
     async fn run(&mut self) -> Result<()> {
-        let torrents = self.qbittorrent.get_torrent_list().await?;
+        self.last_actions.clear();
+        if let Some(qbittorrent) = self.qbittorrent.clone() {
+            self.clean_torrents(qbittorrent.as_ref()).await?;
+        }
+
+        if let Some(usenet) = self.cleanup_config.usenet.clone()
+            && !self.usenet.is_empty()
+        {
+            self.clean_usenet(&usenet).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn clean_torrents(&mut self, qbittorrent: &dyn QBittorrentAPIInterface) -> Result<()> {
+        let torrents = qbittorrent.get_torrent_list().await?;
 
         let mut filters: Vec<Box<dyn TorrentFilter>> = Vec::new();
         filters.push(Box::new(CategoriesFilter::new(
             self.cleanup_config.categories.clone(),
         )));
+        filters.push(Box::new(TagsFilter::new(self.cleanup_config.tags.clone())));
         filters.push(Box::new(TrackerFilter::new(
             self.cleanup_config.ratio,
             self.cleanup_config.trackers.clone(),
+            self.cleanup_config.categories.clone(),
+            self.cleanup_config.tags.clone(),
+        )));
+        filters.push(Box::new(ConditionFilter::new(
+            self.cleanup_config.condition.clone(),
+        )));
+        filters.push(Box::new(ExpiryFilter::new(
+            self.cleanup_config.trackers.clone(),
+            self.cleanup_config.categories.clone(),
         )));
         filters.push(Box::new(SonarrFilter::new(self.sonarr.clone())));
         filters.push(Box::new(RadarrFilter::new(self.radarr.clone())));
 
-        let processed_torrents = self.process_with_filters(torrents, filters).await?;
+        let mut processed_torrents = self.process_with_filters(torrents, filters).await?;
+
+        if let Some(trackers) = &self.cleanup_config.trackers {
+            apply_seeding_limits(trackers, &mut processed_torrents);
+        }
+
+        self.last_evaluation = Some((
+            OffsetDateTime::now_utc(),
+            processed_torrents
+                .iter()
+                .map(|(torrent, data)| TorrentEvaluation::new(torrent, data))
+                .collect(),
+        ));
 
         let mut torrents_to_delete = Vec::new();
-        let mut torrents_ignored = HashMap::new();
         for (torrent, filter_data) in processed_torrents {
-            if filter_data.ignored {
-                torrents_ignored.insert(torrent, filter_data.messages);
-            } else {
+            if !filter_data.keep() {
                 torrents_to_delete.push(torrent);
             }
         }
@@ -581,10 +1227,98 @@ impl CleanupController {
             info!("- {}", torrent.name);
         }
 
+        let dry_run = self.cleanup_config.dry_run.unwrap_or(false);
+        self.last_actions = torrents_to_delete
+            .iter()
+            .filter_map(|torrent| {
+                let action = match self.action_for(torrent) {
+                    CleanupAction::Ignore => return None,
+                    _ if dry_run => "dry_run",
+                    CleanupAction::Delete => "delete",
+                    CleanupAction::Remove => "remove",
+                };
+                Some(ItemAction {
+                    id: torrent.hash.clone(),
+                    name: torrent.name.clone(),
+                    action: action.to_owned(),
+                    reason: None,
+                })
+            })
+            .collect();
+
         info!("Deleted {} torrents", deleted_count);
 
         Ok(())
     }
+
+    /// Deletes the usenet jobs that finished more than `older_than` ago and
+    /// that no *arr queue holds anymore, together with their folders.
+    async fn clean_usenet(&mut self, config: &UsenetCleanupConfig) -> Result<()> {
+        if self.sonarr.is_none() && self.radarr.is_none() {
+            warn!("Not cleaning usenet jobs without Sonarr or Radarr to tell which are imported");
+            return Ok(());
+        }
+        // A job the *arrs still track may not be imported yet, so nothing is
+        // cleaned when any of their queues can't be fetched
+        let mut queued = HashSet::new();
+        for api in [&self.sonarr, &self.radarr].into_iter().flatten() {
+            let queue = api
+                .get_queue()
+                .await
+                .context("Could not get the queue, not cleaning usenet jobs")?;
+            queued.extend(
+                queue
+                    .into_iter()
+                    .filter_map(|resource| resource.download_id)
+                    .map(|download_id| download_id.to_lowercase()),
+            );
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let dry_run = self.cleanup_config.dry_run.unwrap_or(false);
+        for client in &self.usenet {
+            let history = client.get_history().await?;
+            let jobs: Vec<&UsenetJob> = history
+                .iter()
+                .filter(|job| {
+                    job.finished
+                        && job
+                            .completed_at
+                            .is_some_and(|completed_at| completed_at + config.older_than <= now)
+                        && config.categories.contains(&job.category)
+                        && !queued.contains(&job.download_id.to_lowercase())
+                })
+                .collect();
+            if jobs.is_empty() {
+                continue;
+            }
+
+            info!("The following usenet jobs are going to be deleted:");
+            for job in &jobs {
+                info!("- {}", job.name);
+            }
+            if !dry_run {
+                self.shutdown.check("deleting usenet jobs")?;
+                client
+                    .delete_history(jobs.clone(), true)
+                    .await
+                    .map_err(|e| anyhow!("Failed to delete usenet jobs: {}", e))?;
+            }
+            self.last_actions.extend(jobs.into_iter().map(|job| {
+                ItemAction {
+                    id: job.id.clone(),
+                    name: job.name.clone(),
+                    action: if dry_run { "dry_run" } else { "delete" }.to_owned(),
+                    reason: Some(
+                        job.failure
+                            .map_or_else(|| "completed".to_owned(), |failure| failure.to_string()),
+                    ),
+                }
+            }));
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -597,9 +1331,34 @@ impl Task for CleanupController {
         self.run().await
     }
 
+    async fn execute_dry_run(&mut self) -> Result<()> {
+        let dry_run = self.cleanup_config.dry_run.replace(true);
+        let result = self.run().await;
+        self.cleanup_config.dry_run = dry_run;
+        result
+    }
+
     fn next_date(&self, from: time::OffsetDateTime) -> Option<time::OffsetDateTime> {
         self.cleanup_config.schedule.next_date(from)
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.cleanup_config.timeout
+    }
+
+    fn run_on_start(&self) -> bool {
+        self.cleanup_config.run_on_start
+    }
+
+    fn report(&self) -> Option<TaskReport> {
+        self.last_evaluation
+            .as_ref()
+            .map(|(evaluated_at, torrents)| TaskReport::Cleanup {
+                evaluated_at: *evaluated_at,
+                torrents: torrents.clone(),
+                actions: self.last_actions.clone(),
+            })
+    }
 }
 
 #[cfg(test)]
@@ -608,8 +1367,12 @@ mod tests {
     use time::OffsetDateTime;
 
     use super::*;
-    use crate::apis::types::{
-        QueueResource, QueueStatus, SystemStatus, TrackedDownloadState, TrackedDownloadStatus,
+    use crate::{
+        apis::types::{
+            ImportCandidate, MediaItem, QueueResource, QueueStatus, SystemStatus,
+            TrackedDownloadState, TrackedDownloadStatus, UsenetFailure,
+        },
+        config::ByteSize,
     };
 
     // ── helpers ──────────────────────────────────────────────────────────────
@@ -624,6 +1387,9 @@ mod tests {
             ratio: 0.0,
             seeding_time: Duration::from_secs(0),
             progress: 0.0,
+            dlspeed: 0,
+            tags: vec![],
+            added_on: None,
             last_activity: None,
             trackers: vec![],
             contents: vec![],
@@ -650,6 +1416,10 @@ mod tests {
             size: 1_000_000,
             title: Some("title".to_string()),
             download_id: download_id.map(|s| s.to_string()),
+            series_id: None,
+            episode_id: None,
+            episode_ids: vec![],
+            movie_id: None,
             status: QueueStatus::Downloading,
             tracked_download_status: TrackedDownloadStatus::Ok,
             tracked_download_state: TrackedDownloadState::Downloading,
@@ -669,11 +1439,41 @@ mod tests {
         TrackerConfig {
             name: domain.to_string(),
             domain: vec![domain.to_string()],
+            url_pattern: None,
             ratio,
             seeding_time,
             require_both,
             hard_links_percentage: 100,
             ignore,
+            inactive_for: None,
+            size_rule: None,
+            max_torrents: None,
+            max_total_size: None,
+            evict: None,
+        }
+    }
+
+    fn make_tag_config(name: &str) -> TagConfig {
+        TagConfig {
+            name: name.to_string(),
+            ignore: false,
+            opt_in: false,
+            ratio: None,
+            seeding_time: None,
+            require_both: false,
+        }
+    }
+
+    fn make_category_config(name: &str, ignore: bool) -> CategoriesConfig {
+        CategoriesConfig {
+            name: name.to_string(),
+            ignore,
+            ratio: None,
+            seeding_time: None,
+            require_both: false,
+            action: None,
+            inactive_for: None,
+            size_rule: None,
         }
     }
 
@@ -693,11 +1493,21 @@ mod tests {
                 ratio: None,
                 trackers: None,
                 categories: None,
+                tags: None,
+                condition: None,
+                usenet: None,
+                on_filter_error: None,
+                timeout: None,
                 dry_run,
+                run_on_start: false,
             },
-            qbittorrent: qbit,
+            qbittorrent: Some(qbit),
             sonarr: None,
             radarr: None,
+            usenet: Vec::new(),
+            shutdown: ShutdownSignal::default(),
+            last_evaluation: None,
+            last_actions: Vec::new(),
         }
     }
 
@@ -708,11 +1518,21 @@ mod tests {
                 ratio: None,
                 trackers: None,
                 categories: None,
+                tags: None,
+                condition: None,
+                usenet: None,
+                on_filter_error: None,
+                timeout: None,
                 dry_run,
+                run_on_start: false,
             },
-            qbittorrent: mock,
+            qbittorrent: Some(mock),
             sonarr: None,
             radarr: None,
+            usenet: Vec::new(),
+            shutdown: ShutdownSignal::default(),
+            last_evaluation: None,
+            last_actions: Vec::new(),
         }
     }
 
@@ -740,7 +1560,7 @@ mod tests {
         fn name(&self) -> String {
             "AlwaysPassFilter".to_string()
         }
-        async fn filter(&mut self, _torrent: &Torrent) -> Result<TorrentFilterData> {
+        async fn filter(&self, _torrent: &Torrent) -> Result<TorrentFilterData> {
             Ok(TorrentFilterData::pass())
         }
     }
@@ -750,7 +1570,7 @@ mod tests {
         fn name(&self) -> String {
             "AlwaysIgnoreFilter".to_string()
         }
-        async fn filter(&mut self, torrent: &Torrent) -> Result<TorrentFilterData> {
+        async fn filter(&self, torrent: &Torrent) -> Result<TorrentFilterData> {
             Ok(TorrentFilterData::ignored_single_message(format!(
                 "ignoring '{}'",
                 torrent.name
@@ -763,14 +1583,47 @@ mod tests {
         fn name(&self) -> String {
             "AlwaysErrFilter".to_string()
         }
-        async fn filter(&mut self, _torrent: &Torrent) -> Result<TorrentFilterData> {
+        async fn filter(&self, _torrent: &Torrent) -> Result<TorrentFilterData> {
             Err(anyhow::anyhow!("filter error"))
         }
     }
 
+    struct FailClosedErrFilter;
+    struct PrefetchErrFilter;
+
+    #[async_trait]
+    impl TorrentFilter for PrefetchErrFilter {
+        fn name(&self) -> String {
+            "PrefetchErrFilter".to_string()
+        }
+        async fn prefetch(&mut self) -> Result<()> {
+            Err(anyhow::anyhow!("queue unavailable"))
+        }
+        async fn filter(&self, _torrent: &Torrent) -> Result<TorrentFilterData> {
+            Ok(TorrentFilterData::pass())
+        }
+        fn error_policy(&self) -> FilterErrorPolicy {
+            FilterErrorPolicy::FailClosed
+        }
+    }
+
+    #[async_trait]
+    impl TorrentFilter for FailClosedErrFilter {
+        fn name(&self) -> String {
+            "FailClosedErrFilter".to_string()
+        }
+        async fn filter(&self, _torrent: &Torrent) -> Result<TorrentFilterData> {
+            Err(anyhow::anyhow!("service unavailable"))
+        }
+        fn error_policy(&self) -> FilterErrorPolicy {
+            FilterErrorPolicy::FailClosed
+        }
+    }
+
     struct MockArrApi {
         queue: Vec<QueueResource>,
         system_status: SystemStatus,
+        fail_queue: bool,
     }
 
     impl MockArrApi {
@@ -780,6 +1633,14 @@ mod tests {
                 system_status: SystemStatus {
                     start_time: OffsetDateTime::now_utc() - Duration::from_secs(3600),
                 },
+                fail_queue: false,
+            }
+        }
+
+        fn failing_queue() -> Self {
+            Self {
+                fail_queue: true,
+                ..Self::with_queue(vec![])
             }
         }
 
@@ -789,6 +1650,7 @@ mod tests {
                 system_status: SystemStatus {
                     start_time: OffsetDateTime::now_utc(),
                 },
+                fail_queue: false,
             }
         }
     }
@@ -800,6 +1662,9 @@ mod tests {
         }
 
         async fn get_queue(&self) -> Result<Vec<QueueResource>> {
+            if self.fail_queue {
+                anyhow::bail!("queue fetch failed");
+            }
             Ok(self.queue.clone())
         }
 
@@ -813,10 +1678,35 @@ mod tests {
         ) -> Result<()> {
             Ok(())
         }
+
+        async fn search(&self, _ids: Vec<i32>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn unmonitor(&self, _items: Vec<MediaItem>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_import_candidates(&self, _download_id: &str) -> Result<Vec<ImportCandidate>> {
+            Ok(vec![])
+        }
+
+        async fn manual_import(
+            &self,
+            _download_id: &str,
+            _paths: Vec<String>,
+        ) -> Result<Option<i32>> {
+            Ok(None)
+        }
+
+        async fn command_running(&self, _id: i32) -> Result<bool> {
+            Ok(false)
+        }
     }
 
     struct MockQBitApi {
         deleted: Arc<std::sync::Mutex<Vec<String>>>,
+        delete_calls: std::sync::Mutex<Vec<(Vec<String>, Option<bool>)>>,
         delete_result: bool,
         torrent_list: Vec<Torrent>,
     }
@@ -825,6 +1715,7 @@ mod tests {
         fn new() -> Self {
             Self {
                 deleted: Arc::new(std::sync::Mutex::new(vec![])),
+                delete_calls: std::sync::Mutex::new(vec![]),
                 delete_result: true,
                 torrent_list: vec![],
             }
@@ -833,6 +1724,7 @@ mod tests {
         fn failing() -> Self {
             Self {
                 deleted: Arc::new(std::sync::Mutex::new(vec![])),
+                delete_calls: std::sync::Mutex::new(vec![]),
                 delete_result: false,
                 torrent_list: vec![],
             }
@@ -841,6 +1733,7 @@ mod tests {
         fn with_torrents(torrents: Vec<Torrent>) -> Self {
             Self {
                 deleted: Arc::new(std::sync::Mutex::new(vec![])),
+                delete_calls: std::sync::Mutex::new(vec![]),
                 delete_result: true,
                 torrent_list: torrents,
             }
@@ -849,10 +1742,18 @@ mod tests {
         fn deleted_hashes(&self) -> Vec<String> {
             self.deleted.lock().unwrap().clone()
         }
+
+        fn delete_calls(&self) -> Vec<(Vec<String>, Option<bool>)> {
+            self.delete_calls.lock().unwrap().clone()
+        }
     }
 
     #[async_trait]
     impl QBittorrentAPIInterface for MockQBitApi {
+        async fn get_version(&self) -> Result<String> {
+            Ok("v5.0.0".to_string())
+        }
+
         async fn get_torrent_list(&self) -> Result<Vec<Torrent>> {
             Ok(self.torrent_list.clone())
         }
@@ -860,8 +1761,12 @@ mod tests {
         async fn delete_torrents(
             &self,
             torrents: Vec<&Torrent>,
-            _delete_files: Option<bool>,
+            delete_files: Option<bool>,
         ) -> Result<()> {
+            self.delete_calls.lock().unwrap().push((
+                torrents.iter().map(|t| t.hash.clone()).collect(),
+                delete_files,
+            ));
             let mut deleted = self.deleted.lock().unwrap();
             deleted.extend(torrents.iter().map(|t| t.hash.clone()));
             if self.delete_result {
@@ -872,6 +1777,62 @@ mod tests {
         }
     }
 
+    /// A usenet client with `history`, recording the ids of deleted jobs.
+    struct MockUsenetApi {
+        history: Vec<UsenetJob>,
+        deleted: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl MockUsenetApi {
+        fn new(history: Vec<UsenetJob>) -> Self {
+            Self {
+                history,
+                deleted: std::sync::Mutex::new(vec![]),
+            }
+        }
+
+        fn deleted(&self) -> Vec<String> {
+            self.deleted.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl UsenetAPIInterface for MockUsenetApi {
+        async fn get_version(&self) -> Result<String> {
+            Ok("4.3.0".to_string())
+        }
+
+        async fn get_queue(&self) -> Result<Vec<UsenetJob>> {
+            Ok(vec![])
+        }
+
+        async fn get_history(&self) -> Result<Vec<UsenetJob>> {
+            Ok(self.history.clone())
+        }
+
+        async fn delete_history(&self, jobs: Vec<&UsenetJob>, _delete_files: bool) -> Result<()> {
+            self.deleted
+                .lock()
+                .unwrap()
+                .extend(jobs.iter().map(|job| job.id.clone()));
+            Ok(())
+        }
+    }
+
+    fn make_usenet_job(id: &str, category: &str, age: Duration) -> UsenetJob {
+        UsenetJob {
+            id: id.to_string(),
+            download_id: id.to_string(),
+            name: format!("Release {id}"),
+            category: category.to_string(),
+            finished: true,
+            failure: None,
+            fail_message: None,
+            path: Some(format!("/downloads/{id}")),
+            completed_at: Some(OffsetDateTime::now_utc() - age),
+        }
+    }
+
     struct MockArrApiCounted {
         queue: Vec<QueueResource>,
         system_status: SystemStatus,
@@ -920,53 +1881,79 @@ mod tests {
         ) -> Result<()> {
             Ok(())
         }
+
+        async fn search(&self, _ids: Vec<i32>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn unmonitor(&self, _items: Vec<MediaItem>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_import_candidates(&self, _download_id: &str) -> Result<Vec<ImportCandidate>> {
+            Ok(vec![])
+        }
+
+        async fn manual_import(
+            &self,
+            _download_id: &str,
+            _paths: Vec<String>,
+        ) -> Result<Option<i32>> {
+            Ok(None)
+        }
+
+        async fn command_running(&self, _id: i32) -> Result<bool> {
+            Ok(false)
+        }
     }
 
     // ── CategoriesFilter ─────────────────────────────────────────────────────
 
     #[tokio::test]
     async fn categories_filter_no_config_passes() {
-        let mut f = CategoriesFilter::new(None);
+        let f = CategoriesFilter::new(None);
         let t = make_torrent("t", "abc");
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
     async fn categories_filter_matching_ignored_category_is_ignored() {
-        let mut f = CategoriesFilter::new(Some(vec![CategoriesConfig {
-            name: "movies".to_string(),
-            ignore: true,
-        }]));
+        let f = CategoriesFilter::new(Some(vec![make_category_config("movies", true)]));
         let mut t = make_torrent("t", "abc");
         t.category = "movies".to_string();
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
         assert!(!result.messages.is_empty());
     }
 
     #[tokio::test]
     async fn categories_filter_non_ignored_category_passes() {
-        let mut f = CategoriesFilter::new(Some(vec![CategoriesConfig {
-            name: "movies".to_string(),
-            ignore: false,
-        }]));
+        let f = CategoriesFilter::new(Some(vec![make_category_config("movies", false)]));
         let mut t = make_torrent("t", "abc");
         t.category = "movies".to_string();
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
     async fn categories_filter_non_matching_category_passes() {
-        let mut f = CategoriesFilter::new(Some(vec![CategoriesConfig {
-            name: "movies".to_string(),
-            ignore: true,
-        }]));
+        let f = CategoriesFilter::new(Some(vec![make_category_config("movies", true)]));
         let mut t = make_torrent("t", "abc");
         t.category = "tv".to_string();
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
+    }
+
+    #[tokio::test]
+    async fn categories_filter_ignore_action_is_ignored() {
+        let mut cfg = make_category_config("movies", false);
+        cfg.action = Some(CleanupAction::Ignore);
+        let f = CategoriesFilter::new(Some(vec![cfg]));
+        let mut t = make_torrent("t", "abc");
+        t.category = "movies".to_string();
+        let result = f.filter(&t).await.unwrap();
+        assert!(result.keep());
     }
 
     #[test]
@@ -974,14 +1961,52 @@ mod tests {
         assert_eq!(CategoriesFilter::new(None).name(), "CategoriesFilter");
     }
 
+    // ── TagsFilter ───────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn tags_filter_no_config_passes() {
+        let f = TagsFilter::new(None);
+        let t = make_torrent("t", "abc");
+        assert!(!f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn tags_filter_ignored_tag_is_ignored() {
+        let mut keep = make_tag_config("keep");
+        keep.ignore = true;
+        let f = TagsFilter::new(Some(vec![keep]));
+        let mut t = make_torrent("t", "abc");
+        t.tags = vec!["manual".to_string(), "keep".to_string()];
+        let result = f.filter(&t).await.unwrap();
+        assert!(result.keep());
+        assert!(result.protected);
+    }
+
+    #[tokio::test]
+    async fn tags_filter_opt_in_requires_tag() {
+        let mut cleanup = make_tag_config("cleanup");
+        cleanup.opt_in = true;
+        let f = TagsFilter::new(Some(vec![cleanup]));
+        let mut t = make_torrent("t", "abc");
+        assert!(f.filter(&t).await.unwrap().keep());
+
+        t.tags = vec!["cleanup".to_string()];
+        assert!(!f.filter(&t).await.unwrap().keep());
+    }
+
+    #[test]
+    fn tags_filter_name() {
+        assert_eq!(TagsFilter::new(None).name(), "TagsFilter");
+    }
+
     // ── TrackerFilter ────────────────────────────────────────────────────────
 
     #[tokio::test]
     async fn tracker_filter_no_config_passes() {
-        let mut f = TrackerFilter::new(None, None);
+        let f = TrackerFilter::new(None, None, None, None);
         let t = make_torrent("t", "abc");
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -994,12 +2019,12 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(Some(2.0), Some(vec![cfg]));
+        let f = TrackerFilter::new(Some(2.0), Some(vec![cfg]), None, None);
         let mut t = make_torrent("t", "abc");
         t.ratio = 1.0;
         // No trackers on the torrent → configured_trackers is empty → global ratio applies
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
     }
 
     #[tokio::test]
@@ -1011,11 +2036,11 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(Some(2.0), Some(vec![cfg]));
+        let f = TrackerFilter::new(Some(2.0), Some(vec![cfg]), None, None);
         let mut t = make_torrent("t", "abc");
         t.ratio = 2.5;
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1027,11 +2052,11 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(Some(2.0), Some(vec![cfg]));
+        let f = TrackerFilter::new(Some(2.0), Some(vec![cfg]), None, None);
         let mut t = make_torrent("t", "abc");
         t.ratio = 2.5;
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1043,10 +2068,11 @@ mod tests {
             false,
             Some(TrackerIgnore::Always),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let t = torrent_with_tracker("https://tracker.example.com/announce");
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
+        assert!(!result.evictable());
     }
 
     #[tokio::test]
@@ -1058,10 +2084,10 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let t = torrent_with_tracker("https://tracker.example.com/announce");
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1073,11 +2099,12 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
         t.ratio = 1.0;
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
+        assert!(!result.evictable());
     }
 
     #[tokio::test]
@@ -1089,11 +2116,11 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
         t.ratio = 3.0;
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1105,11 +2132,11 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
         t.seeding_time = Duration::from_secs(60);
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
     }
 
     #[tokio::test]
@@ -1121,11 +2148,11 @@ mod tests {
             false,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
         t.seeding_time = Duration::from_secs(7200);
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1137,12 +2164,12 @@ mod tests {
             true,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
         t.ratio = 3.0;
         t.seeding_time = Duration::from_secs(60);
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
     }
 
     #[tokio::test]
@@ -1154,12 +2181,12 @@ mod tests {
             true,
             Some(TrackerIgnore::Never),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
         t.ratio = 3.0;
         t.seeding_time = Duration::from_secs(7200);
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1171,90 +2198,331 @@ mod tests {
             false,
             Some(TrackerIgnore::Always),
         );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
         let t = torrent_with_tracker("https://tracker.example.com/announce");
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_when_hard_linked_ignored_when_percentage_met() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let orig = dir.path().join("movie.mkv");
+        std::fs::write(&orig, b"data").expect("write");
+        let link = dir.path().join("movie_link.mkv");
+        std::fs::hard_link(&orig, &link).expect("hard_link");
+
+        let save_path = dir.path().to_str().unwrap().to_string();
+        let cfg = make_tracker_config(
+            "tracker.example.com",
+            None,
+            None,
+            false,
+            None, // defaults to WhenHardLinked
+        );
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.progress = 1.0;
+        t.save_path = save_path;
+        t.contents = vec![make_content("movie.mkv", 4)];
+
+        let result = f.filter(&t).await.unwrap();
+        assert!(result.keep());
+        assert!(result.evictable());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_when_hard_linked_passes_when_no_hard_links() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("movie.mkv");
+        std::fs::write(&file, b"data").expect("write");
+
+        let save_path = dir.path().to_str().unwrap().to_string();
+        let cfg = make_tracker_config(
+            "tracker.example.com",
+            None,
+            None,
+            false,
+            None, // defaults to WhenHardLinked
+        );
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.progress = 1.0;
+        t.save_path = save_path;
+        t.contents = vec![make_content("movie.mkv", 4)];
+
+        let result = f.filter(&t).await.unwrap();
+        assert!(!result.keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_missing_content_file_skipped_gracefully() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let save_path = dir.path().to_str().unwrap().to_string();
+        let cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        let f = TrackerFilter::new(None, Some(vec![cfg]), None, None);
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.progress = 1.0;
+        t.save_path = save_path;
+        // file does not exist → metadata Err → continue; 0% hard-linked → not ignored
+        t.contents = vec![make_content("nonexistent.mkv", 1000)];
+        let result = f.filter(&t).await.unwrap();
+        assert!(!result.keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_category_ratio_overrides_tracker_ratio() {
+        let cfg = make_tracker_config(
+            "tracker.example.com",
+            Some(2.0),
+            None,
+            false,
+            Some(TrackerIgnore::Never),
+        );
+        let mut tv = make_category_config("tv-sonarr", false);
+        tv.ratio = Some(1.0);
+        let mut movies = make_category_config("movies-radarr", false);
+        movies.ratio = Some(3.0);
+        let f = TrackerFilter::new(None, Some(vec![cfg]), Some(vec![tv, movies]), None);
+
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.ratio = 1.5;
+        t.category = "tv-sonarr".to_string();
+        assert!(!f.filter(&t).await.unwrap().keep());
+
+        t.ratio = 2.5;
+        t.category = "movies-radarr".to_string();
+        assert!(f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_category_without_thresholds_uses_tracker_ratio() {
+        let cfg = make_tracker_config(
+            "tracker.example.com",
+            Some(2.0),
+            None,
+            false,
+            Some(TrackerIgnore::Never),
+        );
+        let category = make_category_config("tv-sonarr", false);
+        let f = TrackerFilter::new(None, Some(vec![cfg]), Some(vec![category]), None);
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.ratio = 1.5;
+        t.category = "tv-sonarr".to_string();
+        assert!(f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_category_thresholds_apply_without_trackers() {
+        let mut category = make_category_config("tv-sonarr", false);
+        category.seeding_time = Some(Duration::from_secs(3600));
+        let f = TrackerFilter::new(Some(0.5), None, Some(vec![category]), None);
+        let mut t = make_torrent("t", "abc");
+        t.category = "tv-sonarr".to_string();
+        t.seeding_time = Duration::from_secs(60);
+        assert!(f.filter(&t).await.unwrap().keep());
+
+        t.seeding_time = Duration::from_secs(7200);
+        assert!(!f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_category_keeps_tracker_ignore() {
+        let cfg = make_tracker_config(
+            "tracker.example.com",
+            None,
+            None,
+            false,
+            Some(TrackerIgnore::Always),
+        );
+        let mut category = make_category_config("tv-sonarr", false);
+        category.ratio = Some(1.0);
+        let f = TrackerFilter::new(None, Some(vec![cfg]), Some(vec![category]), None);
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.ratio = 2.0;
+        t.category = "tv-sonarr".to_string();
+        assert!(f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_tag_thresholds_override_category_and_tracker() {
+        let cfg = make_tracker_config(
+            "tracker.example.com",
+            Some(1.0),
+            None,
+            false,
+            Some(TrackerIgnore::Never),
+        );
+        let mut category = make_category_config("tv", false);
+        category.ratio = Some(1.0);
+        let mut cross_seed = make_tag_config("cross-seed");
+        cross_seed.ratio = Some(3.0);
+        let f = TrackerFilter::new(
+            None,
+            Some(vec![cfg]),
+            Some(vec![category]),
+            Some(vec![cross_seed]),
+        );
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.category = "tv".to_string();
+        t.ratio = 2.0;
+        assert!(!f.filter(&t).await.unwrap().keep());
+
+        t.tags = vec!["cross-seed".to_string()];
+        let result = f.filter(&t).await.unwrap();
+        assert!(result.keep());
+        assert!(!result.protected);
+        assert!(result.messages[0].contains("tag 'cross-seed'"));
+    }
+
+    #[test]
+    fn tracker_filter_name() {
+        assert_eq!(
+            TrackerFilter::new(None, None, None, None).name(),
+            "TrackerFilter"
+        );
+    }
+
+    // ── ConditionFilter ──────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn condition_filter_no_condition_passes() {
+        let f = ConditionFilter::new(None);
+        let t = make_torrent("t", "abc");
+        assert!(!f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn condition_filter_matching_condition_passes() {
+        let condition = "ratio >= 2 or (seeding_time > 14d and tracker == \"tracker.example.com\")"
+            .parse()
+            .unwrap();
+        let f = ConditionFilter::new(Some(condition));
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.seeding_time = Duration::from_secs(15 * 24 * 3600);
+        assert!(!f.filter(&t).await.unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn condition_filter_non_matching_condition_is_ignored() {
+        let condition = "ratio >= 2 or (seeding_time > 14d and tracker == \"other.example.com\")"
+            .parse()
+            .unwrap();
+        let f = ConditionFilter::new(Some(condition));
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.seeding_time = Duration::from_secs(15 * 24 * 3600);
+        let result = f.filter(&t).await.unwrap();
+        assert!(result.keep());
+        assert!(!result.evictable());
+        assert!(result.messages[0].contains("cleanup condition"));
+    }
+
+    #[tokio::test]
+    async fn condition_filter_uses_tags_and_last_activity() {
+        let condition = "tags != keep and last_activity > 30d".parse().unwrap();
+        let f = ConditionFilter::new(Some(condition));
+        let mut t = make_torrent("t", "abc");
+        t.last_activity = Some(OffsetDateTime::now_utc() - Duration::from_secs(31 * 24 * 3600));
+        assert!(!f.filter(&t).await.unwrap().keep());
+
+        t.tags = vec!["keep".to_string()];
+        assert!(f.filter(&t).await.unwrap().keep());
+    }
+
+    #[test]
+    fn condition_filter_name() {
+        assert_eq!(ConditionFilter::new(None).name(), "ConditionFilter");
+    }
+
+    // ── ExpiryFilter ─────────────────────────────────────────────────────────
+
+    fn make_size_rule(larger_than: u64, ratio_below: f64, seeding_time: u64) -> SizeRuleConfig {
+        SizeRuleConfig {
+            larger_than: ByteSize(larger_than),
+            ratio_below: Some(ratio_below),
+            seeding_time: Some(Duration::from_secs(seeding_time)),
+        }
     }
 
     #[tokio::test]
-    async fn tracker_filter_when_hard_linked_ignored_when_percentage_met() {
-        let dir = tempfile::tempdir().expect("tempdir");
-        let orig = dir.path().join("movie.mkv");
-        std::fs::write(&orig, b"data").expect("write");
-        let link = dir.path().join("movie_link.mkv");
-        std::fs::hard_link(&orig, &link).expect("hard_link");
+    async fn expiry_filter_no_config_passes() {
+        let f = ExpiryFilter::new(None, None);
+        let mut t = make_torrent("t", "abc");
+        t.last_activity = Some(OffsetDateTime::now_utc() - Duration::from_secs(3600 * 24 * 365));
+        let result = f.filter(&t).await.unwrap();
+        assert!(result.expired.is_empty());
+    }
 
-        let save_path = dir.path().to_str().unwrap().to_string();
-        let cfg = make_tracker_config(
-            "tracker.example.com",
-            None,
-            None,
-            false,
-            None, // defaults to WhenHardLinked
-        );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+    #[tokio::test]
+    async fn expiry_filter_inactive_tracker_torrent_expires() {
+        let mut cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        cfg.inactive_for = Some(Duration::from_secs(30 * 24 * 3600));
+        let f = ExpiryFilter::new(Some(vec![cfg]), None);
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
-        t.progress = 1.0;
-        t.save_path = save_path;
-        t.contents = vec![make_content("movie.mkv", 4)];
+        t.last_activity = Some(OffsetDateTime::now_utc() - Duration::from_secs(31 * 24 * 3600));
+        assert!(!f.filter(&t).await.unwrap().expired.is_empty());
 
-        let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        t.last_activity = Some(OffsetDateTime::now_utc() - Duration::from_secs(24 * 3600));
+        assert!(f.filter(&t).await.unwrap().expired.is_empty());
     }
 
     #[tokio::test]
-    async fn tracker_filter_when_hard_linked_passes_when_no_hard_links() {
-        let dir = tempfile::tempdir().expect("tempdir");
-        let file = dir.path().join("movie.mkv");
-        std::fs::write(&file, b"data").expect("write");
+    async fn expiry_filter_size_rule_expires_large_poorly_seeded_torrent() {
+        let mut category = make_category_config("movies", false);
+        category.size_rule = Some(make_size_rule(50_000_000_000, 0.5, 7 * 24 * 3600));
+        let f = ExpiryFilter::new(None, Some(vec![category]));
+        let mut t = make_torrent("t", "abc");
+        t.category = "movies".to_string();
+        t.total_size = 60_000_000_000;
+        t.ratio = 0.2;
+        t.seeding_time = Duration::from_secs(8 * 24 * 3600);
+        assert!(!f.filter(&t).await.unwrap().expired.is_empty());
 
-        let save_path = dir.path().to_str().unwrap().to_string();
-        let cfg = make_tracker_config(
-            "tracker.example.com",
-            None,
-            None,
-            false,
-            None, // defaults to WhenHardLinked
-        );
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
-        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
-        t.progress = 1.0;
-        t.save_path = save_path;
-        t.contents = vec![make_content("movie.mkv", 4)];
+        t.ratio = 0.7;
+        assert!(f.filter(&t).await.unwrap().expired.is_empty());
 
-        let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        t.ratio = 0.2;
+        t.seeding_time = Duration::from_secs(24 * 3600);
+        assert!(f.filter(&t).await.unwrap().expired.is_empty());
     }
 
     #[tokio::test]
-    async fn tracker_filter_missing_content_file_skipped_gracefully() {
-        let dir = tempfile::tempdir().expect("tempdir");
-        let save_path = dir.path().to_str().unwrap().to_string();
-        let cfg = make_tracker_config("tracker.example.com", None, None, false, None);
-        let mut f = TrackerFilter::new(None, Some(vec![cfg]));
+    async fn expiry_filter_category_rule_overrides_tracker_rule() {
+        let mut cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        cfg.inactive_for = Some(Duration::from_secs(24 * 3600));
+        let mut category = make_category_config("tv", false);
+        category.inactive_for = Some(Duration::from_secs(30 * 24 * 3600));
+        let f = ExpiryFilter::new(Some(vec![cfg]), Some(vec![category]));
         let mut t = torrent_with_tracker("https://tracker.example.com/announce");
-        t.progress = 1.0;
-        t.save_path = save_path;
-        // file does not exist → metadata Err → continue; 0% hard-linked → not ignored
-        t.contents = vec![make_content("nonexistent.mkv", 1000)];
-        let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        t.category = "tv".to_string();
+        t.last_activity = Some(OffsetDateTime::now_utc() - Duration::from_secs(2 * 24 * 3600));
+        assert!(f.filter(&t).await.unwrap().expired.is_empty());
     }
 
     #[test]
-    fn tracker_filter_name() {
-        assert_eq!(TrackerFilter::new(None, None).name(), "TrackerFilter");
+    fn expiry_filter_name() {
+        assert_eq!(ExpiryFilter::new(None, None).name(), "ExpiryFilter");
+    }
+
+    #[test]
+    fn filter_data_expired_overrides_only_retained() {
+        let mut retained = TorrentFilterData::retained(vec!["ratio".to_string()]);
+        retained += TorrentFilterData::expired("inactive".to_string());
+        assert!(!retained.keep());
+
+        let mut protected = TorrentFilterData::ignored_single_message("queue".to_string());
+        protected += TorrentFilterData::expired("inactive".to_string());
+        assert!(protected.keep());
     }
 
     // ── SonarrFilter ─────────────────────────────────────────────────────────
 
     #[tokio::test]
     async fn sonarr_filter_no_api_passes() {
-        let mut f = SonarrFilter::new(None);
+        let f = SonarrFilter::new(None);
         let t = make_torrent("t", "abc");
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1266,8 +2534,9 @@ mod tests {
         let mut f = SonarrFilter::new(Some(api));
         let mut t = make_torrent("t", "ABC123");
         t.hash = "ABC123".to_string();
+        f.prefetch().await.unwrap();
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
     }
 
     #[tokio::test]
@@ -1278,8 +2547,9 @@ mod tests {
             ))]));
         let mut f = SonarrFilter::new(Some(api));
         let t = make_torrent("t", "ABC123");
+        f.prefetch().await.unwrap();
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1287,9 +2557,7 @@ mod tests {
         let api: Arc<dyn SonarrAndRadarrAPIInterface> =
             Arc::new(MockArrApi::started_recently(vec![]));
         let mut f = SonarrFilter::new(Some(api));
-        let t = make_torrent("t", "abc");
-        let result = f.filter(&t).await;
-        assert!(result.is_err());
+        assert!(f.prefetch().await.is_err());
     }
 
     #[tokio::test]
@@ -1297,12 +2565,13 @@ mod tests {
         let api: Arc<dyn SonarrAndRadarrAPIInterface> = Arc::new(MockArrApi::with_queue(vec![]));
         let mut f = SonarrFilter::new(Some(api));
         let t = make_torrent("t", "abc");
+        f.prefetch().await.unwrap();
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
-    async fn sonarr_filter_queue_cached_on_second_call() {
+    async fn sonarr_filter_queue_fetched_once_per_run() {
         let api = Arc::new(MockArrApiCounted::new(
             vec![make_queue_resource(Some("ABC"))],
             SystemStatus {
@@ -1311,28 +2580,18 @@ mod tests {
         ));
         let api_dyn = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
         let mut f = SonarrFilter::new(Some(api_dyn));
-        let t = make_torrent("t", "xyz");
-        let _ = f.filter(&t).await;
-        let _ = f.filter(&t).await;
+        f.prefetch().await.unwrap();
+        let _ = f.filter(&make_torrent("t1", "xyz")).await;
+        let _ = f.filter(&make_torrent("t2", "abc")).await;
         assert_eq!(api.queue_call_count(), 1);
+        assert_eq!(api.status_call_count(), 0);
     }
 
     #[tokio::test]
-    async fn sonarr_filter_system_status_cached_on_second_call() {
-        // Empty queue triggers system_status fetch; started recently → Err
-        let api = Arc::new(MockArrApiCounted::new(
-            vec![],
-            SystemStatus {
-                start_time: OffsetDateTime::now_utc(),
-            },
-        ));
-        let api_dyn = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
-        let mut f = SonarrFilter::new(Some(api_dyn));
-        let t = make_torrent("t", "xyz");
-        let _ = f.filter(&t).await; // fetches queue + status
-        let _ = f.filter(&t).await; // both from cache
-        assert_eq!(api.queue_call_count(), 1);
-        assert_eq!(api.status_call_count(), 1);
+    async fn sonarr_filter_without_prefetch_errors() {
+        let api: Arc<dyn SonarrAndRadarrAPIInterface> = Arc::new(MockArrApi::with_queue(vec![]));
+        let f = SonarrFilter::new(Some(api));
+        assert!(f.filter(&make_torrent("t", "abc")).await.is_err());
     }
 
     #[test]
@@ -1345,21 +2604,13 @@ mod tests {
     // directly from within this module.
     #[tokio::test]
     async fn sonarr_filter_get_queue_no_api_bails() {
-        let mut f = SonarrFilter {
-            sonarr: None,
-            cached_queue: None,
-            cached_system_status: None,
-        };
+        let f = SonarrFilter::new(None);
         assert!(f.get_queue().await.is_err());
     }
 
     #[tokio::test]
     async fn sonarr_filter_get_system_status_no_api_bails() {
-        let mut f = SonarrFilter {
-            sonarr: None,
-            cached_queue: None,
-            cached_system_status: None,
-        };
+        let f = SonarrFilter::new(None);
         assert!(f.get_system_status().await.is_err());
     }
 
@@ -1367,10 +2618,10 @@ mod tests {
 
     #[tokio::test]
     async fn radarr_filter_no_api_passes() {
-        let mut f = RadarrFilter::new(None);
+        let f = RadarrFilter::new(None);
         let t = make_torrent("t", "abc");
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1382,8 +2633,9 @@ mod tests {
         let mut f = RadarrFilter::new(Some(api));
         let mut t = make_torrent("t", "ABC123");
         t.hash = "ABC123".to_string();
+        f.prefetch().await.unwrap();
         let result = f.filter(&t).await.unwrap();
-        assert!(result.ignored);
+        assert!(result.keep());
     }
 
     #[tokio::test]
@@ -1394,8 +2646,9 @@ mod tests {
             ))]));
         let mut f = RadarrFilter::new(Some(api));
         let t = make_torrent("t", "ABC123");
+        f.prefetch().await.unwrap();
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
@@ -1403,9 +2656,7 @@ mod tests {
         let api: Arc<dyn SonarrAndRadarrAPIInterface> =
             Arc::new(MockArrApi::started_recently(vec![]));
         let mut f = RadarrFilter::new(Some(api));
-        let t = make_torrent("t", "abc");
-        let result = f.filter(&t).await;
-        assert!(result.is_err());
+        assert!(f.prefetch().await.is_err());
     }
 
     #[tokio::test]
@@ -1413,12 +2664,13 @@ mod tests {
         let api: Arc<dyn SonarrAndRadarrAPIInterface> = Arc::new(MockArrApi::with_queue(vec![]));
         let mut f = RadarrFilter::new(Some(api));
         let t = make_torrent("t", "abc");
+        f.prefetch().await.unwrap();
         let result = f.filter(&t).await.unwrap();
-        assert!(!result.ignored);
+        assert!(!result.keep());
     }
 
     #[tokio::test]
-    async fn radarr_filter_queue_cached_on_second_call() {
+    async fn radarr_filter_queue_fetched_once_per_run() {
         let api = Arc::new(MockArrApiCounted::new(
             vec![make_queue_resource(Some("ABC"))],
             SystemStatus {
@@ -1427,27 +2679,18 @@ mod tests {
         ));
         let api_dyn = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
         let mut f = RadarrFilter::new(Some(api_dyn));
-        let t = make_torrent("t", "xyz");
-        let _ = f.filter(&t).await;
-        let _ = f.filter(&t).await;
+        f.prefetch().await.unwrap();
+        let _ = f.filter(&make_torrent("t1", "xyz")).await;
+        let _ = f.filter(&make_torrent("t2", "abc")).await;
         assert_eq!(api.queue_call_count(), 1);
+        assert_eq!(api.status_call_count(), 0);
     }
 
     #[tokio::test]
-    async fn radarr_filter_system_status_cached_on_second_call() {
-        let api = Arc::new(MockArrApiCounted::new(
-            vec![],
-            SystemStatus {
-                start_time: OffsetDateTime::now_utc(),
-            },
-        ));
-        let api_dyn = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
-        let mut f = RadarrFilter::new(Some(api_dyn));
-        let t = make_torrent("t", "xyz");
-        let _ = f.filter(&t).await;
-        let _ = f.filter(&t).await;
-        assert_eq!(api.queue_call_count(), 1);
-        assert_eq!(api.status_call_count(), 1);
+    async fn radarr_filter_without_prefetch_errors() {
+        let api: Arc<dyn SonarrAndRadarrAPIInterface> = Arc::new(MockArrApi::with_queue(vec![]));
+        let f = RadarrFilter::new(Some(api));
+        assert!(f.filter(&make_torrent("t", "abc")).await.is_err());
     }
 
     #[test]
@@ -1460,21 +2703,13 @@ mod tests {
     // directly from within this module.
     #[tokio::test]
     async fn radarr_filter_get_queue_no_api_bails() {
-        let mut f = RadarrFilter {
-            radarr: None,
-            cached_queue: None,
-            cached_system_status: None,
-        };
+        let f = RadarrFilter::new(None);
         assert!(f.get_queue().await.is_err());
     }
 
     #[tokio::test]
     async fn radarr_filter_get_system_status_no_api_bails() {
-        let mut f = RadarrFilter {
-            radarr: None,
-            cached_queue: None,
-            cached_system_status: None,
-        };
+        let f = RadarrFilter::new(None);
         assert!(f.get_system_status().await.is_err());
     }
 
@@ -1512,6 +2747,17 @@ mod tests {
         assert!(deleted.contains(&"hash2".to_string()));
     }
 
+    #[tokio::test]
+    async fn delete_torrents_skips_api_after_shutdown() {
+        let mock = Arc::new(MockQBitApi::new());
+        let ctrl = make_controller(mock.clone(), None);
+        ctrl.shutdown.request();
+        let t = make_torrent("t", "abc");
+        let error = ctrl.delete_torrents(vec![&t]).await.unwrap_err();
+        assert!(error.to_string().contains("Shutdown requested"));
+        assert!(mock.deleted_hashes().is_empty());
+    }
+
     #[tokio::test]
     async fn delete_torrents_api_error_propagates() {
         let mock = Arc::new(MockQBitApi::failing());
@@ -1520,6 +2766,61 @@ mod tests {
         assert!(ctrl.delete_torrents(vec![&t]).await.is_err());
     }
 
+    #[tokio::test]
+    async fn delete_torrents_remove_action_keeps_files() {
+        let mock = Arc::new(MockQBitApi::new());
+        let mut ctrl = make_controller(mock.clone(), None);
+        let mut category = make_category_config("tv", false);
+        category.action = Some(CleanupAction::Remove);
+        ctrl.cleanup_config.categories = Some(vec![category]);
+        let mut t1 = make_torrent("a", "hash1");
+        t1.category = "tv".to_string();
+        let t2 = make_torrent("b", "hash2");
+        let count = ctrl.delete_torrents(vec![&t1, &t2]).await.unwrap();
+        assert_eq!(count, 2);
+        let calls = mock.delete_calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls.contains(&(vec!["hash2".to_string()], Some(true))));
+        assert!(calls.contains(&(vec!["hash1".to_string()], Some(false))));
+    }
+
+    // ── report ───────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn report_contains_last_evaluation() {
+        let mock = Arc::new(MockQBitApi::with_torrents(vec![make_torrent("a", "hash1")]));
+        let mut ctrl = make_run_controller(mock, Some(true));
+        assert!(ctrl.report().is_none());
+        ctrl.run().await.unwrap();
+        let Some(TaskReport::Cleanup {
+            torrents, actions, ..
+        }) = ctrl.report()
+        else {
+            panic!("expected a cleanup report");
+        };
+        assert_eq!(torrents.len(), 1);
+        assert_eq!(torrents[0].hash, "hash1");
+        assert!(!torrents[0].keep);
+        assert_eq!(actions[0].action, "dry_run");
+    }
+
+    #[tokio::test]
+    async fn report_lists_deleted_torrents() {
+        let mock = Arc::new(MockQBitApi::with_torrents(vec![make_torrent("a", "hash1")]));
+        let mut ctrl = make_run_controller(mock, None);
+        ctrl.run().await.unwrap();
+        let report = ctrl.report().unwrap();
+        assert_eq!(
+            report.actions(),
+            &[ItemAction {
+                id: "hash1".to_string(),
+                name: "a".to_string(),
+                action: "delete".to_string(),
+                reason: None,
+            }]
+        );
+    }
+
     // ── process_with_filters ──────────────────────────────────────────────────
 
     #[tokio::test]
@@ -1528,7 +2829,7 @@ mod tests {
         let torrents = vec![make_torrent("a", "h1"), make_torrent("b", "h2")];
         let result = ctrl.process_with_filters(torrents, vec![]).await.unwrap();
         assert_eq!(result.len(), 2);
-        assert!(result.values().all(|d| !d.ignored));
+        assert!(result.values().all(|d| !d.keep()));
     }
 
     #[tokio::test]
@@ -1538,7 +2839,7 @@ mod tests {
         let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(AlwaysIgnoreFilter)];
         let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
         assert_eq!(result.len(), 2);
-        assert!(result.values().all(|d| d.ignored));
+        assert!(result.values().all(|d| d.keep()));
     }
 
     #[tokio::test]
@@ -1547,7 +2848,7 @@ mod tests {
         let torrents = vec![make_torrent("a", "h1")];
         let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(AlwaysPassFilter)];
         let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
-        assert!(!result.values().next().unwrap().ignored);
+        assert!(!result.values().next().unwrap().keep());
     }
 
     #[tokio::test]
@@ -1557,7 +2858,69 @@ mod tests {
         let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(AlwaysErrFilter)];
         let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
         // Error from filter is swallowed; torrent should not be marked ignored
-        assert!(!result.values().next().unwrap().ignored);
+        assert!(!result.values().next().unwrap().keep());
+    }
+
+    #[tokio::test]
+    async fn process_with_filters_fail_closed_filter_keeps_torrents() {
+        let ctrl = make_controller(Arc::new(MockQBitApi::new()), None);
+        let torrents = vec![make_torrent("a", "h1")];
+        let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(FailClosedErrFilter)];
+        let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
+        let data = result.values().next().unwrap();
+        assert!(data.keep());
+        assert!(data.messages[0].contains("FailClosedErrFilter"));
+    }
+
+    #[tokio::test]
+    async fn process_with_filters_failed_prefetch_applies_policy_to_all() {
+        let ctrl = make_controller(Arc::new(MockQBitApi::new()), None);
+        let torrents = vec![make_torrent("a", "h1"), make_torrent("b", "h2")];
+        let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(PrefetchErrFilter)];
+        let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
+        assert!(result.values().all(|d| d.keep()));
+    }
+
+    #[tokio::test]
+    async fn process_with_filters_config_overrides_filter_policy() {
+        let mut ctrl = make_controller(Arc::new(MockQBitApi::new()), None);
+        ctrl.cleanup_config.on_filter_error = Some(FilterErrorPolicy::FailClosed);
+        let torrents = vec![make_torrent("a", "h1")];
+        let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(AlwaysErrFilter)];
+        let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
+        assert!(result.values().all(|d| d.keep()));
+    }
+
+    #[tokio::test]
+    async fn process_with_filters_config_does_not_loosen_filter_policy() {
+        let mut ctrl = make_controller(Arc::new(MockQBitApi::new()), None);
+        ctrl.cleanup_config.on_filter_error = Some(FilterErrorPolicy::FailOpen);
+        let torrents = vec![make_torrent("a", "h1")];
+        let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(FailClosedErrFilter)];
+        let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
+        assert!(result.values().all(|d| d.keep()));
+    }
+
+    #[tokio::test]
+    async fn process_with_filters_abort_policy_errors() {
+        let mut ctrl = make_controller(Arc::new(MockQBitApi::new()), None);
+        ctrl.cleanup_config.on_filter_error = Some(FilterErrorPolicy::Abort);
+        let torrents = vec![make_torrent("a", "h1")];
+        let filters: Vec<Box<dyn TorrentFilter>> = vec![Box::new(FailClosedErrFilter)];
+        let result = ctrl.process_with_filters(torrents, filters).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn arr_filters_fail_closed() {
+        assert_eq!(
+            SonarrFilter::new(None).error_policy(),
+            FilterErrorPolicy::FailClosed
+        );
+        assert_eq!(
+            RadarrFilter::new(None).error_policy(),
+            FilterErrorPolicy::FailClosed
+        );
     }
 
     #[tokio::test]
@@ -1569,10 +2932,144 @@ mod tests {
             vec![Box::new(AlwaysPassFilter), Box::new(AlwaysIgnoreFilter)];
         let result = ctrl.process_with_filters(torrents, filters).await.unwrap();
         let data = result.values().next().unwrap();
-        assert!(data.ignored);
+        assert!(data.keep());
         assert!(!data.messages.is_empty());
     }
 
+    // ── tracker URLs ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn announce_urls_skip_pseudo_trackers() {
+        let mut t = make_torrent("t", "abc");
+        t.trackers = vec![
+            make_tracker_url("** [DHT] **"),
+            make_tracker_url("** [PeX] **"),
+            make_tracker_url("** [LSD] **"),
+            make_tracker_url("udp://10.0.0.5:6969/announce"),
+        ];
+        let urls: Vec<String> = announce_urls(&t).iter().map(|u| u.to_string()).collect();
+        assert_eq!(urls, vec!["udp://10.0.0.5:6969/announce"]);
+    }
+
+    #[test]
+    fn redacted_url_drops_passkey() {
+        let url = Url::parse(
+            "https://user:pw@tracker.example.org:8443/abcdef123456/announce?passkey=secret",
+        )
+        .unwrap();
+        assert_eq!(redacted_url(&url), "https://tracker.example.org:8443");
+    }
+
+    #[tokio::test]
+    async fn tracker_filter_matches_subdomain() {
+        let tracker = make_tracker_config("example.org", Some(2.0), None, false, None);
+        let filter = TrackerFilter::new(None, Some(vec![tracker]), None, None);
+        let mut t = torrent_with_tracker("https://tracker.example.org/passkey/announce");
+        t.ratio = 0.5;
+        let result = filter.filter(&t).await.unwrap();
+        assert!(result.keep());
+    }
+
+    // ── apply_seeding_limits ──────────────────────────────────────────────────
+
+    fn seeding_torrent(hash: &str, ratio: f64, added_secs_ago: u64) -> Torrent {
+        let mut t = torrent_with_tracker("https://tracker.example.com/announce");
+        t.hash = hash.to_string();
+        t.ratio = ratio;
+        t.added_on = Some(OffsetDateTime::now_utc() - Duration::from_secs(added_secs_ago));
+        t
+    }
+
+    fn seeding_data() -> TorrentFilterData {
+        TorrentFilterData::seeding(vec!["hard linked".to_string()])
+    }
+
+    #[test]
+    fn apply_seeding_limits_evicts_lowest_ratio_first() {
+        let mut cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        cfg.max_torrents = Some(2);
+        let mut processed = HashMap::new();
+        processed.insert(seeding_torrent("h1", 3.0, 100), seeding_data());
+        processed.insert(seeding_torrent("h2", 0.5, 200), seeding_data());
+        processed.insert(seeding_torrent("h3", 1.0, 300), seeding_data());
+        apply_seeding_limits(&[cfg], &mut processed);
+        let removed: Vec<&str> = processed
+            .iter()
+            .filter(|(_, d)| !d.keep())
+            .map(|(t, _)| t.hash.as_str())
+            .collect();
+        assert_eq!(removed, vec!["h2"]);
+    }
+
+    #[test]
+    fn apply_seeding_limits_evicts_oldest_first() {
+        let mut cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        cfg.max_torrents = Some(1);
+        cfg.evict = Some(EvictionOrder::Oldest);
+        let mut processed = HashMap::new();
+        processed.insert(seeding_torrent("h1", 3.0, 100), seeding_data());
+        processed.insert(seeding_torrent("h2", 0.5, 200), seeding_data());
+        processed.insert(seeding_torrent("h3", 1.0, 300), seeding_data());
+        apply_seeding_limits(&[cfg], &mut processed);
+        let kept: Vec<&str> = processed
+            .iter()
+            .filter(|(_, d)| d.keep())
+            .map(|(t, _)| t.hash.as_str())
+            .collect();
+        assert_eq!(kept, vec!["h1"]);
+    }
+
+    #[test]
+    fn apply_seeding_limits_respects_thresholds_and_protection() {
+        let mut cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        cfg.max_total_size = Some(ByteSize(1_000_000));
+        let mut processed = HashMap::new();
+        let mut retained = seeding_data();
+        retained += TorrentFilterData::retained(vec!["ratio".to_string()]);
+        let mut protected = seeding_data();
+        protected += TorrentFilterData::ignored_single_message("queue".to_string());
+        processed.insert(seeding_torrent("h1", 0.1, 100), retained);
+        processed.insert(seeding_torrent("h2", 0.2, 200), protected);
+        processed.insert(seeding_torrent("h3", 5.0, 300), seeding_data());
+        apply_seeding_limits(&[cfg], &mut processed);
+        let removed: Vec<&str> = processed
+            .iter()
+            .filter(|(_, d)| !d.keep())
+            .map(|(t, _)| t.hash.as_str())
+            .collect();
+        assert_eq!(removed, vec!["h3"]);
+    }
+
+    #[tokio::test]
+    async fn run_seeding_limits_never_evict_always_ignored_tracker() {
+        let mut cfg = make_tracker_config(
+            "tracker.example.com",
+            None,
+            None,
+            false,
+            Some(TrackerIgnore::Always),
+        );
+        cfg.max_torrents = Some(0);
+        let mock = Arc::new(MockQBitApi::with_torrents(vec![seeding_torrent(
+            "h1", 0.1, 100,
+        )]));
+        let mut ctrl = make_run_controller(mock.clone(), None);
+        ctrl.cleanup_config.trackers = Some(vec![cfg]);
+        ctrl.run().await.unwrap();
+        assert!(mock.deleted_hashes().is_empty());
+    }
+
+    #[test]
+    fn apply_seeding_limits_under_limit_keeps_all() {
+        let mut cfg = make_tracker_config("tracker.example.com", None, None, false, None);
+        cfg.max_torrents = Some(5);
+        let mut processed = HashMap::new();
+        processed.insert(seeding_torrent("h1", 3.0, 100), seeding_data());
+        processed.insert(seeding_torrent("h2", 0.5, 200), seeding_data());
+        apply_seeding_limits(&[cfg], &mut processed);
+        assert!(processed.values().all(|d| d.keep()));
+    }
+
     // ── CleanupController::new ────────────────────────────────────────────────
 
     #[test]
@@ -1583,9 +3080,25 @@ mod tests {
             ratio: None,
             trackers: None,
             categories: None,
+            tags: None,
+            condition: None,
+            usenet: None,
+            on_filter_error: None,
+            timeout: None,
             dry_run: None,
+            run_on_start: false,
         };
-        assert!(CleanupController::new(config, Some(qbit), None, None).is_ok());
+        assert!(
+            CleanupController::new(
+                config,
+                Some(qbit),
+                None,
+                None,
+                Vec::new(),
+                ShutdownSignal::default()
+            )
+            .is_ok()
+        );
     }
 
     #[test]
@@ -1595,9 +3108,62 @@ mod tests {
             ratio: None,
             trackers: None,
             categories: None,
+            tags: None,
+            condition: None,
+            usenet: None,
+            on_filter_error: None,
+            timeout: None,
+            dry_run: None,
+            run_on_start: false,
+        };
+        assert!(
+            CleanupController::new(
+                config,
+                None,
+                None,
+                None,
+                Vec::new(),
+                ShutdownSignal::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn cleanup_controller_cleans_usenet_without_qbit() {
+        let day = Duration::from_secs(24 * 3600);
+        let usenet = Arc::new(MockUsenetApi::new(vec![make_usenet_job(
+            "old",
+            "tv",
+            2 * day,
+        )]));
+        let config = CleanupConfig {
+            schedule: "0 * * * *".parse().unwrap(),
+            ratio: None,
+            trackers: None,
+            categories: None,
+            tags: None,
+            condition: None,
+            usenet: Some(UsenetCleanupConfig {
+                older_than: day,
+                categories: vec!["tv".to_string()],
+            }),
+            on_filter_error: None,
+            timeout: None,
             dry_run: None,
+            run_on_start: false,
         };
-        assert!(CleanupController::new(config, None, None, None).is_err());
+        let mut ctrl = CleanupController::new(
+            config,
+            None,
+            Some(Arc::new(MockArrApi::with_queue(vec![]))),
+            None,
+            vec![usenet.clone() as Arc<dyn UsenetAPIInterface>],
+            ShutdownSignal::default(),
+        )
+        .unwrap();
+        ctrl.run().await.unwrap();
+        assert_eq!(usenet.deleted(), vec!["old".to_string()]);
     }
 
     // ── CleanupController::run ────────────────────────────────────────────────
@@ -1634,6 +3200,16 @@ mod tests {
         assert!(mock.deleted_hashes().is_empty());
     }
 
+    #[tokio::test]
+    async fn execute_dry_run_deletes_nothing_and_restores_config() {
+        let mock = Arc::new(MockQBitApi::with_torrents(vec![make_torrent("a", "hash1")]));
+        let mut ctrl = make_run_controller(mock.clone(), Some(false));
+        ctrl.execute_dry_run().await.unwrap();
+        assert!(mock.deleted_hashes().is_empty());
+        assert_eq!(ctrl.cleanup_config.dry_run, Some(false));
+        assert_eq!(ctrl.report().unwrap().actions()[0].action, "dry_run");
+    }
+
     #[tokio::test]
     async fn run_sonarr_filter_keeps_queued_torrent() {
         let sonarr_api: Arc<dyn SonarrAndRadarrAPIInterface> = Arc::new(MockArrApi::with_queue(
@@ -1649,11 +3225,21 @@ mod tests {
                 ratio: None,
                 trackers: None,
                 categories: None,
+                tags: None,
+                condition: None,
+                usenet: None,
+                on_filter_error: None,
+                timeout: None,
                 dry_run: None,
+                run_on_start: false,
             },
-            qbittorrent: mock.clone(),
+            qbittorrent: Some(mock.clone()),
             sonarr: Some(sonarr_api),
             radarr: None,
+            usenet: Vec::new(),
+            shutdown: ShutdownSignal::default(),
+            last_evaluation: None,
+            last_actions: Vec::new(),
         };
         ctrl.run().await.unwrap();
         let deleted = mock.deleted_hashes();
@@ -1661,10 +3247,132 @@ mod tests {
         assert!(deleted.contains(&"hash2".to_string()));
     }
 
+    #[tokio::test]
+    async fn run_deletes_old_usenet_jobs_not_queued() {
+        let day = Duration::from_secs(24 * 3600);
+        let mut failed = make_usenet_job("failed", "tv", 2 * day);
+        failed.failure = Some(UsenetFailure::RepairFailed);
+        let usenet = Arc::new(MockUsenetApi::new(vec![
+            make_usenet_job("old", "tv", 2 * day),
+            make_usenet_job("recent", "tv", Duration::from_secs(3600)),
+            make_usenet_job("queued", "tv", 2 * day),
+            make_usenet_job("other", "music", 2 * day),
+            failed,
+        ]));
+        let mut ctrl = make_run_controller(Arc::new(MockQBitApi::new()), None);
+        ctrl.sonarr = Some(Arc::new(MockArrApi::with_queue(vec![make_queue_resource(
+            Some("QUEUED"),
+        )])));
+        ctrl.usenet = vec![usenet.clone()];
+        ctrl.cleanup_config.usenet = Some(UsenetCleanupConfig {
+            older_than: day,
+            categories: vec!["tv".to_string()],
+        });
+        ctrl.run().await.unwrap();
+        assert_eq!(
+            usenet.deleted(),
+            vec!["old".to_string(), "failed".to_string()]
+        );
+        let reasons: Vec<_> = ctrl
+            .last_actions
+            .iter()
+            .map(|action| action.reason.as_deref())
+            .collect();
+        assert_eq!(reasons, vec![Some("completed"), Some("repair_failed")]);
+    }
+
+    #[tokio::test]
+    async fn run_keeps_usenet_jobs_without_arr_queue() {
+        let usenet = Arc::new(MockUsenetApi::new(vec![make_usenet_job(
+            "old",
+            "tv",
+            Duration::from_secs(48 * 3600),
+        )]));
+        let mut ctrl = make_run_controller(Arc::new(MockQBitApi::new()), None);
+        ctrl.usenet = vec![usenet.clone()];
+        ctrl.cleanup_config.usenet = Some(UsenetCleanupConfig {
+            older_than: Duration::from_secs(24 * 3600),
+            categories: vec!["tv".to_string()],
+        });
+        // No *arr configured
+        ctrl.run().await.unwrap();
+        assert!(usenet.deleted().is_empty());
+
+        // The queue of the *arr can't be fetched
+        ctrl.sonarr = Some(Arc::new(MockArrApi::failing_queue()));
+        assert!(ctrl.run().await.is_err());
+        assert!(usenet.deleted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_dry_run_keeps_usenet_jobs() {
+        let usenet = Arc::new(MockUsenetApi::new(vec![make_usenet_job(
+            "old",
+            "tv",
+            Duration::from_secs(48 * 3600),
+        )]));
+        let mut ctrl = make_run_controller(Arc::new(MockQBitApi::new()), Some(true));
+        ctrl.sonarr = Some(Arc::new(MockArrApi::with_queue(vec![])));
+        ctrl.usenet = vec![usenet.clone()];
+        ctrl.cleanup_config.usenet = Some(UsenetCleanupConfig {
+            older_than: Duration::from_secs(24 * 3600),
+            categories: vec!["tv".to_string()],
+        });
+        ctrl.run().await.unwrap();
+        assert!(usenet.deleted().is_empty());
+        assert_eq!(ctrl.last_actions[0].action, "dry_run");
+    }
+
+    #[tokio::test]
+    async fn run_inactive_torrent_removed_despite_ratio() {
+        let mut cfg = make_tracker_config(
+            "tracker.example.com",
+            Some(2.0),
+            None,
+            false,
+            Some(TrackerIgnore::Never),
+        );
+        cfg.inactive_for = Some(Duration::from_secs(30 * 24 * 3600));
+        let mut inactive = torrent_with_tracker("https://tracker.example.com/announce");
+        inactive.hash = "hash1".to_string();
+        inactive.last_activity =
+            Some(OffsetDateTime::now_utc() - Duration::from_secs(40 * 24 * 3600));
+        let mut active = torrent_with_tracker("https://tracker.example.com/announce");
+        active.hash = "hash2".to_string();
+        active.last_activity = Some(OffsetDateTime::now_utc());
+        let mock = Arc::new(MockQBitApi::with_torrents(vec![inactive, active]));
+        let mut ctrl = make_run_controller(mock.clone(), None);
+        ctrl.cleanup_config.trackers = Some(vec![cfg]);
+        ctrl.run().await.unwrap();
+        assert_eq!(mock.deleted_hashes(), vec!["hash1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn run_inactive_torrent_kept_when_condition_does_not_match() {
+        let mut cfg = make_tracker_config(
+            "tracker.example.com",
+            None,
+            None,
+            false,
+            Some(TrackerIgnore::Never),
+        );
+        cfg.inactive_for = Some(Duration::from_secs(30 * 24 * 3600));
+        let mut inactive = torrent_with_tracker("https://tracker.example.com/announce");
+        inactive.last_activity =
+            Some(OffsetDateTime::now_utc() - Duration::from_secs(40 * 24 * 3600));
+        let mock = Arc::new(MockQBitApi::with_torrents(vec![inactive]));
+        let mut ctrl = make_run_controller(mock.clone(), None);
+        ctrl.cleanup_config.trackers = Some(vec![cfg]);
+        ctrl.cleanup_config.condition = Some("ratio >= 2".parse().unwrap());
+        ctrl.run().await.unwrap();
+        assert!(mock.deleted_hashes().is_empty());
+    }
+
     #[tokio::test]
     async fn run_delete_failure_propagates_error() {
         let mock = Arc::new(MockQBitApi {
             deleted: Arc::new(std::sync::Mutex::new(vec![])),
+            delete_calls: std::sync::Mutex::new(vec![]),
             delete_result: false,
             torrent_list: vec![make_torrent("a", "hash1")],
         });
@@ -1,19 +1,34 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use log::info;
+use log::{info, warn};
+use serde::Serialize;
 use time::OffsetDateTime;
 
 use crate::{
     apis::{
-        SonarrAndRadarrAPIInterface,
-        types::{QueueResource, QueueStatus, TrackedDownloadState, TrackedDownloadStatus},
+        QBittorrentAPIInterface, SonarrAndRadarrAPIInterface, UsenetAPIInterface,
+        types::{
+            ImportCandidate, MediaItem, QueueResource, QueueStatus, TrackedDownloadState,
+            TrackedDownloadStatus, UsenetJob,
+        },
+    },
+    config::{
+        ByteSize, ContentsConfig, ImportBlockedConfig, RetryConfig, RetryScope, SlowDownloadConfig,
+        StuckConfig,
     },
-    config::RetryConfig,
-    tasks::Task,
+    notifications::Notifier,
+    tasks::{ItemAction, ShutdownSignal, Task, TaskReport},
 };
 
+#[derive(Clone)]
 struct StrikeData {
     num: usize,
     last_sizeleft: i64,
@@ -30,6 +45,124 @@ impl StrikeData {
     }
 }
 
+/// Progress of a download at the previous run, used to compute its speed.
+#[derive(Clone)]
+struct SpeedSample {
+    sizeleft: i64,
+    at: OffsetDateTime,
+    slow_checks: usize,
+}
+
+/// A finished download the *arr does not import on its own.
+#[derive(Clone)]
+struct BlockedImport {
+    /// When it was first seen blocked, or last seen importing
+    since: OffsetDateTime,
+    /// Whether the manual import was already tried
+    attempted: bool,
+    /// The *arr command running the manual import
+    command: Option<i32>,
+}
+
+/// Strikes collected so far by a stalled download.
+#[derive(Clone, Serialize, Debug)]
+pub struct StrikeReport {
+    pub download_id: String,
+    pub strikes: usize,
+    pub max_strikes: usize,
+    #[serde(with = "time::serde::rfc3339")]
+    pub last_check: OffsetDateTime,
+}
+
+/// Rule of the retry task that matched a queue item.
+#[derive(Clone, Copy, Serialize, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryRule {
+    Stuck,
+    ZeroProgress,
+    Stalled,
+    SlowDownload,
+    BannedMessage,
+    ImportBlocked,
+    UsenetFailure,
+    BadContents,
+}
+
+impl fmt::Display for RetryRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RetryRule::Stuck => "stuck",
+            RetryRule::ZeroProgress => "zero_progress",
+            RetryRule::Stalled => "stalled",
+            RetryRule::SlowDownload => "slow_download",
+            RetryRule::BannedMessage => "banned_message",
+            RetryRule::ImportBlocked => "import_blocked",
+            RetryRule::UsenetFailure => "usenet_failure",
+            RetryRule::BadContents => "bad_contents",
+        })
+    }
+}
+
+/// Why the retry task acted on a queue item, with the state the item was in.
+#[derive(Clone, Serialize, Debug)]
+pub struct QueueVerdict {
+    /// Missing for releases the *arr has not sent to a download client yet
+    pub download_id: Option<String>,
+    pub title: String,
+    pub rule: RetryRule,
+    /// What the rule found, such as the strike count or the banned message
+    pub detail: String,
+    pub status: QueueStatus,
+    pub tracked_download_state: TrackedDownloadState,
+    pub size: i64,
+    pub sizeleft: i64,
+}
+
+impl QueueVerdict {
+    fn new(resource: &QueueResource, rule: RetryRule, detail: String) -> Self {
+        Self {
+            download_id: resource.download_id.clone(),
+            title: resource.title.clone().unwrap_or_default(),
+            rule,
+            detail,
+            status: resource.status,
+            tracked_download_state: resource.tracked_download_state,
+            size: resource.size,
+            sizeleft: resource.sizeleft,
+        }
+    }
+}
+
+impl fmt::Display for QueueVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let downloaded = if self.size > 0 {
+            (self.size - self.sizeleft) as f64 * 100.0 / self.size as f64
+        } else {
+            0.0
+        };
+        write!(
+            f,
+            "{} ({}), {:?}/{:?}, {downloaded:.0}% of {} bytes downloaded",
+            self.rule, self.detail, self.status, self.tracked_download_state, self.size
+        )
+    }
+}
+
+/// A queue item to remove and why.
+type Removal = (QueueResource, QueueVerdict);
+
+/// What the download clients report about queued downloads, keyed by
+/// lowercase download id.
+#[derive(Default)]
+struct ClientReports {
+    /// Download speeds reported by qBittorrent
+    speeds: HashMap<String, i64>,
+    /// Names of the files of each torrent, empty until its metadata arrived
+    files: HashMap<String, Vec<String>>,
+    /// Usenet jobs that failed in a way `usenet.failures` covers
+    usenet_failures: HashMap<String, UsenetJob>,
+}
+
 const BANNED_MESSAGES: [&str; 3] = [
     "Found potentially dangerous file",
     "Invalid video file, unsupported extension",
@@ -43,8 +176,18 @@ pub struct RetryController {
     retry_config: RetryConfig,
     sonarr: Arc<dyn SonarrAndRadarrAPIInterface>,
     radarr: Arc<dyn SonarrAndRadarrAPIInterface>,
+    qbittorrent: Option<Arc<dyn QBittorrentAPIInterface>>,
+    usenet: Vec<Arc<dyn UsenetAPIInterface>>,
+    notifier: Option<Notifier>,
+    shutdown: ShutdownSignal,
 
     strikes: HashMap<String, StrikeData>,
+    speeds: HashMap<String, SpeedSample>,
+    /// When releases of each item were removed, within the `max_retries` window
+    removals: HashMap<MediaItem, Vec<OffsetDateTime>>,
+    blocked: HashMap<String, BlockedImport>,
+    last_verdicts: Vec<QueueVerdict>,
+    last_actions: Vec<ItemAction>,
 }
 
 impl RetryController {
@@ -52,6 +195,10 @@ impl RetryController {
         retry_config: RetryConfig,
         sonarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
         radarr: Option<Arc<dyn SonarrAndRadarrAPIInterface>>,
+        qbittorrent: Option<Arc<dyn QBittorrentAPIInterface>>,
+        usenet: Vec<Arc<dyn UsenetAPIInterface>>,
+        notifier: Option<Notifier>,
+        shutdown: ShutdownSignal,
     ) -> Result<Self> {
         sonarr
             .zip(radarr)
@@ -59,7 +206,16 @@ impl RetryController {
                 retry_config,
                 sonarr,
                 radarr,
+                qbittorrent,
+                usenet,
+                notifier,
+                shutdown,
                 strikes: HashMap::new(),
+                speeds: HashMap::new(),
+                removals: HashMap::new(),
+                blocked: HashMap::new(),
+                last_verdicts: Vec::new(),
+                last_actions: Vec::new(),
             })
             .context("Could not initialize retry task")
     }
@@ -115,6 +271,28 @@ impl RetryController {
         false
     }
 
+    /// Returns `true` when the resource has been waiting in a queued, delayed,
+    /// client-unavailable or metadata state for longer than its limit.
+    fn is_stuck(stuck: &StuckConfig, resource: &QueueResource, now: OffsetDateTime) -> bool {
+        let downloading_metadata = resource
+            .error_message
+            .as_ref()
+            .is_some_and(|v| v.to_lowercase().contains("downloading metadata"));
+        let limit = if downloading_metadata {
+            stuck.metadata
+        } else {
+            match resource.status {
+                QueueStatus::Queued => stuck.queued,
+                QueueStatus::Delay => stuck.delay,
+                QueueStatus::DownloadClientUnavailable => stuck.download_client_unavailable,
+                _ => None,
+            }
+        };
+        limit
+            .zip(resource.added)
+            .is_some_and(|(limit, added)| now > added + limit)
+    }
+
     /// Returns `true` when the download was added more than 1 hour ago and
     /// zero bytes have been transferred.
     fn is_zero_progress_timeout(resource: &QueueResource, now: OffsetDateTime) -> bool {
@@ -123,117 +301,753 @@ impl RetryController {
         })
     }
 
-    /// Returns `true` when the resource is `Completed / Warning / ImportPending`
-    /// and any status message contains one of the `BANNED_MESSAGES` strings.
-    fn has_banned_import_message(resource: &QueueResource) -> bool {
+    /// Minimum speed of `resource`, which depends on the size of the release.
+    fn min_speed(slow: &SlowDownloadConfig, resource: &QueueResource) -> ByteSize {
+        match &slow.large {
+            Some(large) if resource.size as u64 >= large.larger_than.0 => large.min_speed,
+            _ => slow.min_speed,
+        }
+    }
+
+    /// Records the speed of an active download, taken from `dlspeed` when
+    /// qBittorrent reports it and from the `sizeleft` delta since the previous
+    /// run otherwise. Returns `true` (removing the entry) once the download
+    /// has been below its minimum speed for the configured number of checks.
+    fn check_slow_download(
+        &mut self,
+        download_id: &str,
+        resource: &QueueResource,
+        dlspeed: Option<i64>,
+        now: OffsetDateTime,
+    ) -> bool {
+        let Some(slow) = &self.retry_config.slow else {
+            return false;
+        };
+        if resource.status != QueueStatus::Downloading
+            || resource.tracked_download_state != TrackedDownloadState::Downloading
+            || resource.sizeleft <= 0
+        {
+            self.speeds.remove(download_id);
+            return false;
+        }
+
+        let min_speed = Self::min_speed(slow, resource);
+        let previous = self.speeds.get(download_id);
+        let speed = dlspeed.map(|speed| speed as f64).or_else(|| {
+            previous.filter(|sample| now > sample.at).map(|sample| {
+                (sample.sizeleft - resource.sizeleft) as f64 / (now - sample.at).as_seconds_f64()
+            })
+        });
+        let mut slow_checks = previous.map_or(0, |sample| sample.slow_checks);
+        if let Some(speed) = speed {
+            if speed < min_speed.0 as f64 {
+                slow_checks += 1;
+                info!(
+                    "Download '{}' is slow ({:.0} B/s), checks {}/{}",
+                    resource.title.as_deref().unwrap_or("Unknown"),
+                    speed,
+                    slow_checks,
+                    slow.checks
+                );
+            } else {
+                slow_checks = 0;
+            }
+        }
+
+        if slow_checks >= slow.checks {
+            self.speeds.remove(download_id);
+            return true;
+        }
+        self.speeds.insert(
+            download_id.to_owned(),
+            SpeedSample {
+                sizeleft: resource.sizeleft,
+                at: now,
+                slow_checks,
+            },
+        );
+        false
+    }
+
+    /// Explains why the `files` of a torrent give away a fake or
+    /// password-protected release, or returns `None` when they look genuine.
+    fn bad_contents(contents: &ContentsConfig, files: &[String]) -> Option<String> {
+        // Torrents list no files until their metadata arrived
+        if files.is_empty() {
+            return None;
+        }
+        let has_extension = |file: &str, extensions: &[String]| {
+            Path::new(file)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| {
+                    extensions.iter().any(|listed| {
+                        listed
+                            .trim_start_matches('.')
+                            .eq_ignore_ascii_case(extension)
+                    })
+                })
+        };
+        if let Some(file) = files
+            .iter()
+            .find(|file| has_extension(file, &contents.denied_extensions))
+        {
+            return Some(format!("denied file {file}"));
+        }
+        if contents.require_video
+            && !files
+                .iter()
+                .any(|file| has_extension(file, &contents.video_extensions))
+        {
+            return Some(format!("no video file among {} files", files.len()));
+        }
+        None
+    }
+
+    /// Returns the first status message containing one of the `BANNED_MESSAGES`
+    /// strings when the resource is `Completed / Warning / ImportPending`.
+    fn banned_import_message(resource: &QueueResource) -> Option<&str> {
+        if resource.status != QueueStatus::Completed
+            || resource.tracked_download_status != TrackedDownloadStatus::Warning
+            || resource.tracked_download_state != TrackedDownloadState::ImportPending
+        {
+            return None;
+        }
+        resource
+            .status_messages
+            .iter()
+            .flat_map(|msg_group| &msg_group.messages)
+            .find(|msg| BANNED_MESSAGES.iter().any(|banned| msg.contains(banned)))
+            .map(String::as_str)
+    }
+
+    /// Returns `true` when the resource is a finished download left in
+    /// `ImportBlocked` or `ImportPending` with a warning.
+    fn is_import_blocked(resource: &QueueResource) -> bool {
         resource.status == QueueStatus::Completed
             && resource.tracked_download_status == TrackedDownloadStatus::Warning
-            && resource.tracked_download_state == TrackedDownloadState::ImportPending
-            && resource.status_messages.iter().any(|msg_group| {
-                msg_group
-                    .messages
-                    .iter()
-                    .any(|msg| BANNED_MESSAGES.iter().any(|banned| msg.contains(banned)))
-            })
+            && matches!(
+                resource.tracked_download_state,
+                TrackedDownloadState::ImportBlocked | TrackedDownloadState::ImportPending
+            )
+    }
+
+    /// Returns the file to import when the download holds a single file,
+    /// matching the episode or movie of `resource` and with no rejection.
+    fn safe_import<'a>(
+        resource: &QueueResource,
+        candidates: &'a [ImportCandidate],
+    ) -> Option<&'a ImportCandidate> {
+        let [candidate] = candidates else {
+            return None;
+        };
+        let matches = match (resource.episode_id, resource.movie_id) {
+            (Some(episode_id), _) => {
+                candidate.series_id == resource.series_id && candidate.episode_ids == [episode_id]
+            }
+            (None, Some(movie_id)) => candidate.movie_id == Some(movie_id),
+            (None, None) => false,
+        };
+        (matches && candidate.rejections.is_empty()).then_some(candidate)
+    }
+
+    /// Imports the blocked download `resource` through the manual import when
+    /// that is safe. Skips the import when dry-run is enabled. Returns the id
+    /// of the command running the import, if one was started.
+    async fn manual_import(
+        &mut self,
+        api: &Arc<dyn SonarrAndRadarrAPIInterface>,
+        resource: &QueueResource,
+        download_id: &str,
+    ) -> Result<Option<i32>> {
+        let title = resource.title.as_deref().unwrap_or("Unknown");
+        let candidates = match api.get_import_candidates(download_id).await {
+            Ok(candidates) => candidates,
+            Err(e) => {
+                warn!("Could not list files of '{title}': {e}");
+                return Ok(None);
+            }
+        };
+        let Some(candidate) = Self::safe_import(resource, &candidates) else {
+            info!("'{title}' is blocked from importing and can't be imported safely");
+            return Ok(None);
+        };
+        let verdict = QueueVerdict::new(
+            resource,
+            RetryRule::ImportBlocked,
+            format!("single matching file {}", candidate.path),
+        );
+        let dry_run = self.retry_config.dry_run.unwrap_or(false);
+        let mut command = None;
+        if dry_run {
+            info!("Dry run enabled, not importing '{title}': {verdict}");
+        } else {
+            self.shutdown.check("importing downloads")?;
+            match api
+                .manual_import(download_id, vec![candidate.path.clone()])
+                .await
+            {
+                Ok(id) => command = id,
+                Err(e) => {
+                    warn!("Could not import '{title}': {e}");
+                    return Ok(None);
+                }
+            }
+            info!("Imported '{title}': {verdict}");
+        }
+        self.last_actions.push(ItemAction {
+            id: download_id.to_owned(),
+            name: title.to_owned(),
+            action: if dry_run { "dry_run" } else { "manual_import" }.to_owned(),
+            reason: Some(verdict.to_string()),
+        });
+        self.last_verdicts.push(verdict);
+        Ok(command)
+    }
+
+    /// Tries the manual import the first time `resource` is seen blocked, and
+    /// returns `true` once it was blocked for longer than the timeout. The
+    /// timeout restarts while the *arr still runs the import, so a slow
+    /// import is not removed halfway.
+    async fn check_import_blocked(
+        &mut self,
+        api: &Arc<dyn SonarrAndRadarrAPIInterface>,
+        config: &ImportBlockedConfig,
+        resource: &QueueResource,
+        download_id: &str,
+        now: OffsetDateTime,
+    ) -> Result<bool> {
+        let blocked = self
+            .blocked
+            .entry(download_id.to_owned())
+            .or_insert(BlockedImport {
+                since: now,
+                attempted: false,
+                command: None,
+            });
+        if let Some(id) = blocked.command {
+            match api.command_running(id).await {
+                Ok(true) => {
+                    blocked.since = now;
+                    return Ok(false);
+                }
+                Ok(false) => blocked.command = None,
+                Err(e) => {
+                    let title = resource.title.as_deref().unwrap_or("Unknown");
+                    warn!("Could not check the import of '{title}': {e}");
+                    blocked.command = None;
+                }
+            }
+        }
+        let since = blocked.since;
+        if config.manual_import && !blocked.attempted {
+            blocked.attempted = true;
+            let command = self.manual_import(api, resource, download_id).await?;
+            if let Some(blocked) = self.blocked.get_mut(download_id) {
+                blocked.command = command;
+            }
+            return Ok(false);
+        }
+        Ok(config.timeout.is_some_and(|timeout| now >= since + timeout))
     }
 
     /// Calls `queue_bulk_delete` for `items`, setting the blocklist flag
-    /// according to `blocklist`. Skips the API call when dry-run is enabled.
+    /// according to `blocklist`, and logs the verdict of each. When
+    /// `redownload` is set the *arr may grab a replacement, or it is searched
+    /// for explicitly when `search` is enabled. Skips the API calls when
+    /// dry-run is enabled. Returns what was done to each item.
     async fn execute_removals(
         &self,
         api: &Arc<dyn SonarrAndRadarrAPIInterface>,
-        items: Vec<QueueResource>,
+        items: Vec<Removal>,
         blocklist: bool,
-    ) -> Result<()> {
+        redownload: bool,
+    ) -> Result<Vec<ItemAction>> {
         if items.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
-        let titles: Vec<&String> = items.iter().filter_map(|r| r.title.as_ref()).collect();
-        if self.retry_config.dry_run.unwrap_or(false) {
-            if blocklist {
-                info!("Dry run enabled, not removing and blocking: {titles:?}");
-            } else {
-                info!("Dry run enabled, not removing: {titles:?}");
-            }
-        } else {
-            if blocklist {
-                info!("Following queue removed and blocked: {titles:?}");
-            } else {
-                info!("Following queue removed: {titles:?}");
+        let dry_run = self.retry_config.dry_run.unwrap_or(false);
+        let actions = items
+            .iter()
+            .map(|(r, verdict)| ItemAction {
+                id: r.download_id.clone().unwrap_or_default(),
+                name: r.title.clone().unwrap_or_default(),
+                action: match (dry_run, blocklist) {
+                    (true, _) => "dry_run",
+                    (false, true) => "remove_and_blocklist",
+                    (false, false) => "remove",
+                }
+                .to_owned(),
+                reason: Some(verdict.to_string()),
+            })
+            .collect();
+        for (r, verdict) in &items {
+            let title = r.title.as_deref().unwrap_or("Unknown");
+            match (dry_run, blocklist) {
+                (true, true) => {
+                    info!("Dry run enabled, not removing and blocking '{title}': {verdict}")
+                }
+                (true, false) => info!("Dry run enabled, not removing '{title}': {verdict}"),
+                (false, true) => info!("Removing and blocking '{title}': {verdict}"),
+                (false, false) => info!("Removing '{title}': {verdict}"),
             }
+        }
+        if !dry_run {
+            self.shutdown.check("removing queue items")?;
+            let search = redownload && self.retry_config.search;
+            // Season packs cover more episodes than the one of their item
+            let mut search_ids: Vec<i32> = items
+                .iter()
+                .flat_map(|(r, _)| {
+                    r.episode_ids
+                        .iter()
+                        .copied()
+                        .chain(r.episode_id)
+                        .chain(r.movie_id)
+                })
+                .collect();
+            search_ids.sort_unstable();
+            search_ids.dedup();
+            // The *arr's own re-download would grab a second replacement
+            // next to the one found by the explicit search
             api.queue_bulk_delete(
-                items.into_iter().map(|r| r.id).collect(),
+                items.into_iter().map(|(r, _)| r.id).collect(),
                 Some(true),
                 Some(blocklist),
-                Some(false),
+                Some(!redownload || search),
                 Some(false),
             )
             .await?;
+            if search && !search_ids.is_empty() {
+                // The items are already gone, so a failed search must not
+                // hide that from the run's result
+                if self.shutdown.is_requested() {
+                    info!("Shutdown requested, not searching for replacements");
+                } else if let Err(e) = api.search(search_ids).await {
+                    warn!("Could not search for replacements: {e}");
+                }
+            }
+        }
+        Ok(actions)
+    }
+
+    /// What removals of `resource` count against for `max_retries`, if enabled.
+    fn retry_target(&self, resource: &QueueResource) -> Option<MediaItem> {
+        let max_retries = self.retry_config.max_retries.as_ref()?;
+        let series = resource
+            .series_id
+            .filter(|_| max_retries.per == RetryScope::Series)
+            .map(MediaItem::Series);
+        series
+            .or(resource.episode_id.map(MediaItem::Episode))
+            .or(resource.movie_id.map(MediaItem::Movie))
+    }
+
+    /// Records a removal of `target` and returns `true` once it was removed
+    /// `max_retries.count` times within the window.
+    fn record_removal(&mut self, target: MediaItem, now: OffsetDateTime) -> bool {
+        let Some(max_retries) = &self.retry_config.max_retries else {
+            return false;
+        };
+        let removals = self.removals.entry(target).or_default();
+        removals.retain(|at| *at + max_retries.window > now);
+        removals.push(now);
+        removals.len() >= max_retries.count
+    }
+
+    /// Splits off the items that run out of retries with this removal.
+    /// `pending` counts the removals of each target so far, including the
+    /// ones of this run that are only recorded once they succeeded.
+    fn split_exhausted(
+        &self,
+        items: Vec<Removal>,
+        pending: &mut HashMap<MediaItem, usize>,
+        now: OffsetDateTime,
+    ) -> (Vec<Removal>, Vec<Removal>) {
+        let Some(max_retries) = &self.retry_config.max_retries else {
+            return (items, Vec::new());
+        };
+        let mut retry = Vec::new();
+        let mut exhausted = Vec::new();
+        for item in items {
+            let Some(target) = self.retry_target(&item.0) else {
+                retry.push(item);
+                continue;
+            };
+            let count = pending.entry(target).or_insert_with(|| {
+                self.removals.get(&target).map_or(0, |removals| {
+                    removals
+                        .iter()
+                        .filter(|at| **at + max_retries.window > now)
+                        .count()
+                })
+            });
+            *count += 1;
+            if *count >= max_retries.count {
+                exhausted.push(item);
+            } else {
+                retry.push(item);
+            }
+        }
+        (retry, exhausted)
+    }
+
+    /// Stops retrying `items` that ran out of retries: unmonitors them when
+    /// configured and sends a notification for each. Does nothing in dry-run.
+    async fn give_up(&self, api: &Arc<dyn SonarrAndRadarrAPIInterface>, items: &[QueueResource]) {
+        let Some(max_retries) = &self.retry_config.max_retries else {
+            return;
+        };
+        if items.is_empty() || self.retry_config.dry_run.unwrap_or(false) {
+            return;
+        }
+        let mut targets: Vec<MediaItem> =
+            items.iter().filter_map(|r| self.retry_target(r)).collect();
+        targets.sort_unstable();
+        targets.dedup();
+        let unmonitored = max_retries.unmonitor
+            && match api.unmonitor(targets).await {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Could not unmonitor items out of retries: {e}");
+                    false
+                }
+            };
+        let Some(notifier) = &self.notifier else {
+            return;
+        };
+        for resource in items {
+            let title = resource.title.as_deref().unwrap_or("Unknown");
+            notifier.send(
+                "Giving up on a download".to_owned(),
+                format!(
+                    "'{title}' was removed {} times within {}, not retrying it{}",
+                    max_retries.count,
+                    humantime::format_duration(max_retries.window),
+                    if unmonitored {
+                        " and unmonitored it"
+                    } else {
+                        ""
+                    }
+                ),
+            );
         }
-        Ok(())
     }
 
     async fn process_queue(
         &mut self,
         api: &Arc<dyn SonarrAndRadarrAPIInterface>,
         items: Vec<QueueResource>,
+        reports: &ClientReports,
     ) -> Result<()> {
         let now = OffsetDateTime::now_utc();
         let mut to_remove = Vec::new();
         let mut to_remove_and_blocklist = Vec::new();
 
         for resource in items {
+            // Delayed releases have no download id yet, so this is checked first
+            if let Some(stuck) = &self.retry_config.stuck
+                && Self::is_stuck(stuck, &resource, now)
+            {
+                let age = resource.added.map_or(Duration::ZERO, |added| {
+                    Duration::from_secs((now - added).whole_seconds().max(0) as u64)
+                });
+                let verdict = QueueVerdict::new(
+                    &resource,
+                    RetryRule::Stuck,
+                    format!("added {} ago", humantime::format_duration(age)),
+                );
+                if stuck.blocklist {
+                    to_remove_and_blocklist.push((resource, verdict));
+                } else {
+                    to_remove.push((resource, verdict));
+                }
+                continue;
+            }
+
             let Some(download_id) = resource.download_id.as_ref() else {
                 continue;
             };
 
-            let mut remove = false;
-            let mut blocklist = false;
+            // Every rule is checked, as some track state across runs, and the
+            // first one that matched explains the removal
+            let mut verdict = reports
+                .usenet_failures
+                .get(&download_id.to_lowercase())
+                .and_then(|job| {
+                    let failure = job.failure?;
+                    let message = job.fail_message.as_deref().unwrap_or_default();
+                    Some(QueueVerdict::new(
+                        &resource,
+                        RetryRule::UsenetFailure,
+                        format!("{failure}: {message}"),
+                    ))
+                });
+
+            // Caught before the *arr reports a dangerous file after the download
+            if let Some(contents) = &self.retry_config.contents
+                && let Some(files) = reports.files.get(&download_id.to_lowercase())
+                && let Some(detail) = Self::bad_contents(contents, files)
+            {
+                verdict.get_or_insert_with(|| {
+                    QueueVerdict::new(&resource, RetryRule::BadContents, detail)
+                });
+            }
 
             if resource.status == QueueStatus::Warning {
                 if Self::is_zero_progress_timeout(&resource, now) {
-                    remove = true;
-                    blocklist = true;
+                    verdict.get_or_insert_with(|| {
+                        QueueVerdict::new(
+                            &resource,
+                            RetryRule::ZeroProgress,
+                            "nothing downloaded 1h after being added".to_owned(),
+                        )
+                    });
                 } else if Self::is_stalled_download(&resource)
                     && self.check_stalled_strikes(download_id, &resource, now)
                 {
-                    remove = true;
-                    blocklist = true;
+                    verdict.get_or_insert_with(|| {
+                        QueueVerdict::new(
+                            &resource,
+                            RetryRule::Stalled,
+                            format!("{MAX_NUM_STRIKES}/{MAX_NUM_STRIKES} strikes"),
+                        )
+                    });
                 }
             } else if let Some(strike) = self.strikes.get_mut(download_id) {
                 strike.last_check = now;
             }
 
-            if Self::has_banned_import_message(&resource) {
-                remove = true;
-                blocklist = true;
+            let dlspeed = reports.speeds.get(&download_id.to_lowercase()).copied();
+            if self.check_slow_download(download_id, &resource, dlspeed, now)
+                && verdict.is_none()
+                && let Some(slow) = &self.retry_config.slow
+            {
+                verdict = Some(QueueVerdict::new(
+                    &resource,
+                    RetryRule::SlowDownload,
+                    format!(
+                        "below {} B/s for {} checks",
+                        Self::min_speed(slow, &resource).0,
+                        slow.checks
+                    ),
+                ));
             }
 
-            if remove {
-                if blocklist {
-                    to_remove_and_blocklist.push(resource);
-                } else {
-                    to_remove.push(resource);
+            if let Some(message) = Self::banned_import_message(&resource) {
+                verdict.get_or_insert_with(|| {
+                    QueueVerdict::new(&resource, RetryRule::BannedMessage, message.to_owned())
+                });
+            } else if let Some(config) = self.retry_config.import_blocked.clone()
+                && Self::is_import_blocked(&resource)
+                && self
+                    .check_import_blocked(api, &config, &resource, download_id, now)
+                    .await?
+                && verdict.is_none()
+            {
+                let timeout = config.timeout.unwrap_or_default();
+                verdict = Some(QueueVerdict::new(
+                    &resource,
+                    RetryRule::ImportBlocked,
+                    format!(
+                        "blocked from importing for over {}",
+                        humantime::format_duration(timeout)
+                    ),
+                ));
+            }
+
+            // Unlike stuck items, these releases are broken and never retried
+            if let Some(verdict) = verdict {
+                to_remove_and_blocklist.push((resource, verdict));
+            }
+        }
+
+        let mut pending = HashMap::new();
+        let (to_remove, exhausted) = self.split_exhausted(to_remove, &mut pending, now);
+        let (to_remove_and_blocklist, exhausted_and_blocklist) =
+            self.split_exhausted(to_remove_and_blocklist, &mut pending, now);
+        let exhausted_items: Vec<QueueResource> = exhausted
+            .iter()
+            .chain(&exhausted_and_blocklist)
+            .map(|(r, _)| r.clone())
+            .collect();
+
+        for (items, blocklist, redownload) in [
+            (to_remove, false, true),
+            (to_remove_and_blocklist, true, true),
+            (exhausted, false, false),
+            (exhausted_and_blocklist, true, false),
+        ] {
+            self.last_verdicts
+                .extend(items.iter().map(|(_, verdict)| verdict.clone()));
+            let targets: Vec<MediaItem> = items
+                .iter()
+                .filter_map(|(r, _)| self.retry_target(r))
+                .collect();
+            let actions = self
+                .execute_removals(api, items, blocklist, redownload)
+                .await?;
+            // Only removals that happened count against the retries
+            if !self.retry_config.dry_run.unwrap_or(false) {
+                for target in targets {
+                    self.record_removal(target, now);
                 }
             }
+            self.last_actions.extend(actions);
         }
+        self.give_up(api, &exhausted_items).await;
 
-        self.execute_removals(api, to_remove, false).await?;
-        self.execute_removals(api, to_remove_and_blocklist, true)
-            .await?;
+        Ok(())
+    }
+
+    /// Download speeds and file names reported by qBittorrent, keyed by
+    /// lowercase hash. Empty when neither the slow download nor the contents
+    /// rule is enabled or qBittorrent is unavailable, in which case speeds are
+    /// computed from `sizeleft` instead.
+    async fn torrent_reports(&self) -> (HashMap<String, i64>, HashMap<String, Vec<String>>) {
+        let Some(qbittorrent) = &self.qbittorrent else {
+            return Default::default();
+        };
+        if self.retry_config.slow.is_none() && self.retry_config.contents.is_none() {
+            return Default::default();
+        }
+        match qbittorrent.get_torrent_list().await {
+            Ok(torrents) => torrents
+                .into_iter()
+                .map(|t| {
+                    let hash = t.hash.to_lowercase();
+                    let files = t.contents.into_iter().map(|c| c.name).collect();
+                    ((hash.clone(), t.dlspeed), (hash, files))
+                })
+                .unzip(),
+            Err(e) => {
+                warn!("Could not get torrents from qBittorrent: {e}");
+                Default::default()
+            }
+        }
+    }
+
+    /// Queued and history jobs of every usenet client, or nothing when the
+    /// usenet rules are disabled. Clients that can't be reached are skipped.
+    async fn usenet_jobs(&self) -> Vec<(Arc<dyn UsenetAPIInterface>, Vec<UsenetJob>)> {
+        if self.retry_config.usenet.is_none() {
+            return Vec::new();
+        }
+        let mut jobs = Vec::new();
+        for client in &self.usenet {
+            match tokio::try_join!(client.get_queue(), client.get_history()) {
+                Ok((queue, history)) => jobs.push((
+                    Arc::clone(client),
+                    queue.into_iter().chain(history).collect(),
+                )),
+                Err(e) => warn!("Could not get usenet jobs: {e}"),
+            }
+        }
+        jobs
+    }
 
+    /// Deletes failed jobs no *arr queue holds anymore from the history of
+    /// their client, along with their folders.
+    async fn clean_failed_jobs(
+        &mut self,
+        usenet_jobs: &[(Arc<dyn UsenetAPIInterface>, Vec<UsenetJob>)],
+        queued: &HashSet<String>,
+    ) -> Result<()> {
+        let dry_run = self.retry_config.dry_run.unwrap_or(false);
+        for (client, jobs) in usenet_jobs {
+            let failed: Vec<&UsenetJob> = jobs
+                .iter()
+                .filter(|job| {
+                    job.finished
+                        && job.failure.is_some()
+                        && !queued.contains(&job.download_id.to_lowercase())
+                })
+                .collect();
+            if failed.is_empty() {
+                continue;
+            }
+            let names: Vec<&str> = failed.iter().map(|job| job.name.as_str()).collect();
+            if dry_run {
+                info!("Dry run enabled, not deleting failed usenet jobs: {names:?}");
+            } else {
+                self.shutdown.check("deleting failed usenet jobs")?;
+                if let Err(e) = client.delete_history(failed.clone(), true).await {
+                    warn!("Could not delete failed usenet jobs {names:?}: {e}");
+                    continue;
+                }
+                info!("Deleted failed usenet jobs: {names:?}");
+            }
+            self.last_actions
+                .extend(failed.into_iter().map(|job| ItemAction {
+                    id: job.id.clone(),
+                    name: job.name.clone(),
+                    action: if dry_run { "dry_run" } else { "delete" }.to_owned(),
+                    reason: job.fail_message.clone(),
+                }));
+        }
         Ok(())
     }
 
     async fn run(&mut self) -> Result<()> {
+        self.last_verdicts.clear();
+        self.last_actions.clear();
         let (sonarr_items, radarr_items) =
             tokio::try_join!(self.sonarr.get_queue(), self.radarr.get_queue())?;
 
+        // Forget downloads that left the queue so a re-grab starts fresh
+        let queued: HashSet<String> = sonarr_items
+            .iter()
+            .chain(&radarr_items)
+            .filter_map(|r| r.download_id.clone())
+            .collect();
+        self.speeds
+            .retain(|download_id, _| queued.contains(download_id));
+        self.blocked
+            .retain(|download_id, _| queued.contains(download_id));
+        if let Some(max_retries) = &self.retry_config.max_retries {
+            let now = OffsetDateTime::now_utc();
+            self.removals.retain(|_, removals| {
+                removals.retain(|at| *at + max_retries.window > now);
+                !removals.is_empty()
+            });
+        }
+
+        let usenet_jobs = self.usenet_jobs().await;
+        let usenet_failures = match &self.retry_config.usenet {
+            Some(usenet) => usenet_jobs
+                .iter()
+                .flat_map(|(_, jobs)| jobs)
+                .filter(|job| {
+                    job.failure
+                        .is_some_and(|failure| usenet.failures.contains(&failure))
+                })
+                .map(|job| (job.download_id.to_lowercase(), job.clone()))
+                .collect(),
+            None => HashMap::new(),
+        };
+        let (speeds, files) = self.torrent_reports().await;
+        let reports = ClientReports {
+            speeds,
+            files,
+            usenet_failures,
+        };
+
         let sonarr = Arc::clone(&self.sonarr);
-        self.process_queue(&sonarr, sonarr_items).await?;
+        self.process_queue(&sonarr, sonarr_items, &reports).await?;
 
         let radarr = Arc::clone(&self.radarr);
-        self.process_queue(&radarr, radarr_items).await?;
+        self.process_queue(&radarr, radarr_items, &reports).await?;
+
+        // Jobs removed from the queues above are deleted by their *arr
+        if self
+            .retry_config
+            .usenet
+            .as_ref()
+            .is_some_and(|usenet| usenet.clean_failed)
+        {
+            let queued = queued.iter().map(|id| id.to_lowercase()).collect();
+            self.clean_failed_jobs(&usenet_jobs, &queued).await?;
+        }
 
         Ok(())
     }
@@ -249,21 +1063,75 @@ impl Task for RetryController {
         self.run().await
     }
 
+    async fn execute_dry_run(&mut self) -> Result<()> {
+        // Strikes given during a dry run would make the next real run remove
+        // items earlier than it should.
+        let strikes = self.strikes.clone();
+        let speeds = self.speeds.clone();
+        let removals = self.removals.clone();
+        let blocked = self.blocked.clone();
+        let dry_run = self.retry_config.dry_run.replace(true);
+        let result = self.run().await;
+        self.retry_config.dry_run = dry_run;
+        self.strikes = strikes;
+        self.speeds = speeds;
+        self.removals = removals;
+        self.blocked = blocked;
+        result
+    }
+
     fn next_date(&self, from: time::OffsetDateTime) -> Option<time::OffsetDateTime> {
         self.retry_config.schedule.next_date(from)
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.retry_config.timeout
+    }
+
+    fn run_on_start(&self) -> bool {
+        self.retry_config.run_on_start
+    }
+
+    fn report(&self) -> Option<TaskReport> {
+        let mut strikes: Vec<StrikeReport> = self
+            .strikes
+            .iter()
+            .map(|(download_id, strike)| StrikeReport {
+                download_id: download_id.clone(),
+                strikes: strike.num,
+                max_strikes: MAX_NUM_STRIKES,
+                last_check: strike.last_check,
+            })
+            .collect();
+        strikes.sort_by(|a, b| a.download_id.cmp(&b.download_id));
+        Some(TaskReport::Retry {
+            strikes,
+            verdicts: self.last_verdicts.clone(),
+            actions: self.last_actions.clone(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
     use anyhow::Result;
     use async_trait::async_trait;
     use time::OffsetDateTime;
 
     use super::*;
-    use crate::apis::types::{
-        QueueStatus, SystemStatus, TrackedDownloadState, TrackedDownloadStatus,
-        TrackedDownloadStatusMessage,
+    use crate::{
+        apis::{
+            qbittorrent::Torrent,
+            types::{
+                QueueStatus, SystemStatus, TrackedDownloadState, TrackedDownloadStatus,
+                TrackedDownloadStatusMessage, UsenetFailure,
+            },
+        },
+        config::{
+            ByteSize, LargeReleaseConfig, MaxRetriesConfig, SlowDownloadConfig, UsenetRetryConfig,
+        },
     };
 
     // ── helpers ──────────────────────────────────────────────────────────────
@@ -275,6 +1143,10 @@ mod tests {
             size: 1_000_000,
             title: Some("Test Torrent".to_string()),
             download_id: Some("abc123".to_string()),
+            series_id: None,
+            episode_id: None,
+            episode_ids: vec![],
+            movie_id: None,
             status: QueueStatus::Downloading,
             tracked_download_status: TrackedDownloadStatus::Ok,
             tracked_download_state: TrackedDownloadState::Downloading,
@@ -304,10 +1176,27 @@ mod tests {
                 schedule: "0 * * * *".parse().unwrap(),
                 timeout: None,
                 dry_run: None,
+                run_on_start: false,
+                slow: None,
+                stuck: None,
+                search: false,
+                max_retries: None,
+                import_blocked: None,
+                usenet: None,
+                contents: None,
             },
             sonarr: Arc::new(MockArrApi::new()),
             radarr: Arc::new(MockArrApi::new()),
+            qbittorrent: None,
+            usenet: Vec::new(),
+            notifier: None,
+            shutdown: ShutdownSignal::default(),
             strikes: HashMap::new(),
+            speeds: HashMap::new(),
+            removals: HashMap::new(),
+            blocked: HashMap::new(),
+            last_verdicts: Vec::new(),
+            last_actions: Vec::new(),
         }
     }
 
@@ -316,7 +1205,14 @@ mod tests {
     struct MockArrApi {
         queue: Vec<QueueResource>,
         fail_queue: bool,
+        fail_delete: bool,
         bulk_delete_calls: std::sync::Mutex<Vec<(Vec<i32>, Option<bool>)>>,
+        search_calls: std::sync::Mutex<Vec<Vec<i32>>>,
+        unmonitor_calls: std::sync::Mutex<Vec<Vec<MediaItem>>>,
+        candidates: Vec<ImportCandidate>,
+        import_calls: std::sync::Mutex<Vec<(String, Vec<String>)>>,
+        /// Whether the started manual import is still running
+        import_running: AtomicBool,
     }
 
     impl MockArrApi {
@@ -324,7 +1220,13 @@ mod tests {
             Self {
                 queue: vec![],
                 fail_queue: false,
+                fail_delete: false,
                 bulk_delete_calls: std::sync::Mutex::new(vec![]),
+                search_calls: std::sync::Mutex::new(vec![]),
+                unmonitor_calls: std::sync::Mutex::new(vec![]),
+                candidates: vec![],
+                import_calls: std::sync::Mutex::new(vec![]),
+                import_running: AtomicBool::new(false),
             }
         }
 
@@ -332,7 +1234,13 @@ mod tests {
             Self {
                 queue,
                 fail_queue: false,
+                fail_delete: false,
                 bulk_delete_calls: std::sync::Mutex::new(vec![]),
+                search_calls: std::sync::Mutex::new(vec![]),
+                unmonitor_calls: std::sync::Mutex::new(vec![]),
+                candidates: vec![],
+                import_calls: std::sync::Mutex::new(vec![]),
+                import_running: AtomicBool::new(false),
             }
         }
 
@@ -340,13 +1248,31 @@ mod tests {
             Self {
                 queue: vec![],
                 fail_queue: true,
+                fail_delete: false,
                 bulk_delete_calls: std::sync::Mutex::new(vec![]),
+                search_calls: std::sync::Mutex::new(vec![]),
+                unmonitor_calls: std::sync::Mutex::new(vec![]),
+                candidates: vec![],
+                import_calls: std::sync::Mutex::new(vec![]),
+                import_running: AtomicBool::new(false),
             }
         }
 
         fn delete_calls(&self) -> Vec<(Vec<i32>, Option<bool>)> {
             self.bulk_delete_calls.lock().unwrap().clone()
         }
+
+        fn search_calls(&self) -> Vec<Vec<i32>> {
+            self.search_calls.lock().unwrap().clone()
+        }
+
+        fn unmonitor_calls(&self) -> Vec<Vec<MediaItem>> {
+            self.unmonitor_calls.lock().unwrap().clone()
+        }
+
+        fn import_calls(&self) -> Vec<(String, Vec<String>)> {
+            self.import_calls.lock().unwrap().clone()
+        }
     }
 
     #[async_trait]
@@ -372,65 +1298,212 @@ mod tests {
             _skip_redownload: Option<bool>,
             _change_category: Option<bool>,
         ) -> Result<()> {
+            if self.fail_delete {
+                anyhow::bail!("bulk delete failed");
+            }
             self.bulk_delete_calls
                 .lock()
                 .unwrap()
                 .push((ids, blocklist));
             Ok(())
         }
-    }
 
-    // ── is_stalled_download ───────────────────────────────────────────────────
+        async fn search(&self, ids: Vec<i32>) -> Result<()> {
+            self.search_calls.lock().unwrap().push(ids);
+            Ok(())
+        }
 
-    #[test]
-    fn is_stalled_download_true_when_all_conditions_met() {
-        let mut r = make_resource();
-        r.status = QueueStatus::Warning;
-        r.tracked_download_state = TrackedDownloadState::Downloading;
-        r.error_message = Some("The download is stalled with no connections".to_string());
-        assert!(RetryController::is_stalled_download(&r));
-    }
+        async fn unmonitor(&self, items: Vec<MediaItem>) -> Result<()> {
+            self.unmonitor_calls.lock().unwrap().push(items);
+            Ok(())
+        }
 
-    #[test]
-    fn is_stalled_download_false_when_not_warning_status() {
-        let mut r = make_resource();
-        r.status = QueueStatus::Downloading;
-        r.tracked_download_state = TrackedDownloadState::Downloading;
-        r.error_message = Some("The download is stalled".to_string());
-        assert!(!RetryController::is_stalled_download(&r));
-    }
+        async fn get_import_candidates(&self, _download_id: &str) -> Result<Vec<ImportCandidate>> {
+            Ok(self.candidates.clone())
+        }
 
-    #[test]
-    fn is_stalled_download_false_when_not_downloading_state() {
-        let mut r = make_resource();
-        r.status = QueueStatus::Warning;
-        r.tracked_download_state = TrackedDownloadState::ImportPending;
-        r.error_message = Some("The download is stalled".to_string());
-        assert!(!RetryController::is_stalled_download(&r));
-    }
+        async fn manual_import(
+            &self,
+            download_id: &str,
+            paths: Vec<String>,
+        ) -> Result<Option<i32>> {
+            self.import_calls
+                .lock()
+                .unwrap()
+                .push((download_id.to_owned(), paths));
+            Ok(Some(1))
+        }
 
-    #[test]
-    fn is_stalled_download_false_when_no_stall_message() {
-        let mut r = make_resource();
-        r.status = QueueStatus::Warning;
-        r.tracked_download_state = TrackedDownloadState::Downloading;
-        r.error_message = None;
-        assert!(!RetryController::is_stalled_download(&r));
+        async fn command_running(&self, _id: i32) -> Result<bool> {
+            Ok(self.import_running.load(Ordering::Relaxed))
+        }
     }
 
-    #[test]
-    fn is_stalled_download_false_when_different_error_message() {
-        let mut r = make_resource();
-        r.status = QueueStatus::Warning;
-        r.tracked_download_state = TrackedDownloadState::Downloading;
-        r.error_message = Some("Some other error".to_string());
-        assert!(!RetryController::is_stalled_download(&r));
+    /// qBittorrent reporting the given download speed for each hash.
+    struct MockQBitApi {
+        speeds: Vec<(String, i64)>,
     }
 
-    // ── is_zero_progress_timeout ──────────────────────────────────────────────
+    #[async_trait]
+    impl QBittorrentAPIInterface for MockQBitApi {
+        async fn get_version(&self) -> Result<String> {
+            Ok("v5.0.0".to_string())
+        }
 
-    #[test]
-    fn is_zero_progress_timeout_true_when_old_and_no_progress() {
+        async fn get_torrent_list(&self) -> Result<Vec<Torrent>> {
+            Ok(self
+                .speeds
+                .iter()
+                .map(|(hash, dlspeed)| Torrent {
+                    name: hash.clone(),
+                    hash: hash.clone(),
+                    total_size: 1_000_000,
+                    save_path: "/downloads".to_string(),
+                    category: String::new(),
+                    ratio: 0.0,
+                    seeding_time: Duration::from_secs(0),
+                    progress: 0.0,
+                    dlspeed: *dlspeed,
+                    tags: vec![],
+                    added_on: None,
+                    last_activity: None,
+                    trackers: vec![],
+                    contents: vec![],
+                })
+                .collect())
+        }
+
+        async fn delete_torrents(
+            &self,
+            _torrents: Vec<&Torrent>,
+            _delete_files: Option<bool>,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A usenet client holding `jobs`, recording the ids of deleted jobs.
+    struct MockUsenetApi {
+        jobs: Vec<UsenetJob>,
+        deleted: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl MockUsenetApi {
+        fn new(jobs: Vec<UsenetJob>) -> Self {
+            Self {
+                jobs,
+                deleted: std::sync::Mutex::new(vec![]),
+            }
+        }
+
+        fn deleted(&self) -> Vec<String> {
+            self.deleted.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl UsenetAPIInterface for MockUsenetApi {
+        async fn get_version(&self) -> Result<String> {
+            Ok("4.3.0".to_string())
+        }
+
+        async fn get_queue(&self) -> Result<Vec<UsenetJob>> {
+            Ok(self.jobs.iter().filter(|j| !j.finished).cloned().collect())
+        }
+
+        async fn get_history(&self) -> Result<Vec<UsenetJob>> {
+            Ok(self.jobs.iter().filter(|j| j.finished).cloned().collect())
+        }
+
+        async fn delete_history(&self, jobs: Vec<&UsenetJob>, _delete_files: bool) -> Result<()> {
+            self.deleted
+                .lock()
+                .unwrap()
+                .extend(jobs.iter().map(|job| job.id.clone()));
+            Ok(())
+        }
+    }
+
+    fn failed_job(download_id: &str, failure: UsenetFailure) -> UsenetJob {
+        UsenetJob {
+            id: format!("nzo_{download_id}"),
+            download_id: download_id.to_string(),
+            name: format!("Release {download_id}"),
+            category: "tv".to_string(),
+            finished: true,
+            failure: Some(failure),
+            fail_message: Some("Repair failed, not enough repair blocks".to_string()),
+            path: None,
+            completed_at: None,
+        }
+    }
+
+    fn usenet_controller(
+        sonarr: Arc<MockArrApi>,
+        usenet: Arc<MockUsenetApi>,
+        clean_failed: bool,
+    ) -> RetryController {
+        let mut ctrl = make_controller();
+        ctrl.sonarr = sonarr;
+        ctrl.usenet = vec![usenet];
+        ctrl.retry_config.usenet = Some(UsenetRetryConfig {
+            failures: vec![UsenetFailure::RepairFailed, UsenetFailure::Encrypted],
+            clean_failed,
+        });
+        ctrl
+    }
+
+    // ── is_stalled_download ───────────────────────────────────────────────────
+
+    #[test]
+    fn is_stalled_download_true_when_all_conditions_met() {
+        let mut r = make_resource();
+        r.status = QueueStatus::Warning;
+        r.tracked_download_state = TrackedDownloadState::Downloading;
+        r.error_message = Some("The download is stalled with no connections".to_string());
+        assert!(RetryController::is_stalled_download(&r));
+    }
+
+    #[test]
+    fn is_stalled_download_false_when_not_warning_status() {
+        let mut r = make_resource();
+        r.status = QueueStatus::Downloading;
+        r.tracked_download_state = TrackedDownloadState::Downloading;
+        r.error_message = Some("The download is stalled".to_string());
+        assert!(!RetryController::is_stalled_download(&r));
+    }
+
+    #[test]
+    fn is_stalled_download_false_when_not_downloading_state() {
+        let mut r = make_resource();
+        r.status = QueueStatus::Warning;
+        r.tracked_download_state = TrackedDownloadState::ImportPending;
+        r.error_message = Some("The download is stalled".to_string());
+        assert!(!RetryController::is_stalled_download(&r));
+    }
+
+    #[test]
+    fn is_stalled_download_false_when_no_stall_message() {
+        let mut r = make_resource();
+        r.status = QueueStatus::Warning;
+        r.tracked_download_state = TrackedDownloadState::Downloading;
+        r.error_message = None;
+        assert!(!RetryController::is_stalled_download(&r));
+    }
+
+    #[test]
+    fn is_stalled_download_false_when_different_error_message() {
+        let mut r = make_resource();
+        r.status = QueueStatus::Warning;
+        r.tracked_download_state = TrackedDownloadState::Downloading;
+        r.error_message = Some("Some other error".to_string());
+        assert!(!RetryController::is_stalled_download(&r));
+    }
+
+    // ── is_zero_progress_timeout ──────────────────────────────────────────────
+
+    #[test]
+    fn is_zero_progress_timeout_true_when_old_and_no_progress() {
         let mut r = make_resource();
         r.added = Some(OffsetDateTime::now_utc() - Duration::from_secs(7200));
         r.size = 1_000_000;
@@ -473,10 +1546,10 @@ mod tests {
         assert!(!RetryController::is_zero_progress_timeout(&r, now));
     }
 
-    // ── has_banned_import_message ─────────────────────────────────────────────
+    // ── banned_import_message ─────────────────────────────────────────────────
 
     #[test]
-    fn has_banned_import_message_true_on_match() {
+    fn banned_import_message_found_on_match() {
         let mut r = make_resource();
         r.status = QueueStatus::Completed;
         r.tracked_download_status = TrackedDownloadStatus::Warning;
@@ -485,11 +1558,14 @@ mod tests {
             title: None,
             messages: vec!["Found potentially dangerous file in download".to_string()],
         }];
-        assert!(RetryController::has_banned_import_message(&r));
+        assert_eq!(
+            RetryController::banned_import_message(&r),
+            Some("Found potentially dangerous file in download")
+        );
     }
 
     #[test]
-    fn has_banned_import_message_false_on_no_message_match() {
+    fn banned_import_message_none_on_no_message_match() {
         let mut r = make_resource();
         r.status = QueueStatus::Completed;
         r.tracked_download_status = TrackedDownloadStatus::Warning;
@@ -498,11 +1574,11 @@ mod tests {
             title: None,
             messages: vec!["Everything looks fine".to_string()],
         }];
-        assert!(!RetryController::has_banned_import_message(&r));
+        assert!(RetryController::banned_import_message(&r).is_none());
     }
 
     #[test]
-    fn has_banned_import_message_false_on_wrong_status() {
+    fn banned_import_message_none_on_wrong_status() {
         let mut r = make_resource();
         r.status = QueueStatus::Downloading; // not Completed
         r.tracked_download_status = TrackedDownloadStatus::Warning;
@@ -511,11 +1587,11 @@ mod tests {
             title: None,
             messages: vec!["Found potentially dangerous file".to_string()],
         }];
-        assert!(!RetryController::has_banned_import_message(&r));
+        assert!(RetryController::banned_import_message(&r).is_none());
     }
 
     #[test]
-    fn has_banned_import_message_false_on_wrong_tracked_status() {
+    fn banned_import_message_none_on_wrong_tracked_status() {
         let mut r = make_resource();
         r.status = QueueStatus::Completed;
         r.tracked_download_status = TrackedDownloadStatus::Ok; // not Warning
@@ -524,7 +1600,53 @@ mod tests {
             title: None,
             messages: vec!["Found potentially dangerous file".to_string()],
         }];
-        assert!(!RetryController::has_banned_import_message(&r));
+        assert!(RetryController::banned_import_message(&r).is_none());
+    }
+
+    // ── bad_contents ──────────────────────────────────────────────────────────
+
+    fn contents_config() -> ContentsConfig {
+        ContentsConfig {
+            denied_extensions: vec!["exe".to_string(), ".lnk".to_string()],
+            require_video: true,
+            video_extensions: vec!["mkv".to_string(), "mp4".to_string()],
+        }
+    }
+
+    fn files(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn bad_contents_none_without_metadata() {
+        assert!(RetryController::bad_contents(&contents_config(), &[]).is_none());
+    }
+
+    #[test]
+    fn bad_contents_none_for_video_release() {
+        let files = files(&["Show.S01E01/Show.S01E01.MKV", "Show.S01E01/Show.nfo"]);
+        assert!(RetryController::bad_contents(&contents_config(), &files).is_none());
+    }
+
+    #[test]
+    fn bad_contents_denied_extension() {
+        let files = files(&["Show.S01E01/Show.S01E01.mkv", "Show.S01E01/Codec.lnk"]);
+        assert_eq!(
+            RetryController::bad_contents(&contents_config(), &files).as_deref(),
+            Some("denied file Show.S01E01/Codec.lnk")
+        );
+    }
+
+    #[test]
+    fn bad_contents_no_video_file() {
+        let files = files(&["Show.S01E01/Show.S01E01.rar", "Show.S01E01/Password.txt"]);
+        assert_eq!(
+            RetryController::bad_contents(&contents_config(), &files).as_deref(),
+            Some("no video file among 2 files")
+        );
+        let mut config = contents_config();
+        config.require_video = false;
+        assert!(RetryController::bad_contents(&config, &files).is_none());
     }
 
     // ── check_stalled_strikes ─────────────────────────────────────────────────
@@ -595,16 +1717,205 @@ mod tests {
         assert!(!ctrl.strikes.contains_key("abc123"));
     }
 
+    // ── is_stuck ──────────────────────────────────────────────────────────────
+
+    fn stuck_config() -> StuckConfig {
+        StuckConfig {
+            metadata: Some(Duration::from_secs(30 * 60)),
+            queued: Some(Duration::from_secs(6 * 3600)),
+            delay: None,
+            download_client_unavailable: Some(Duration::from_secs(3600)),
+            blocklist: false,
+        }
+    }
+
+    fn added_ago(status: QueueStatus, secs: u64) -> QueueResource {
+        let mut r = make_resource();
+        r.status = status;
+        r.added = Some(OffsetDateTime::now_utc() - Duration::from_secs(secs));
+        r
+    }
+
+    #[test]
+    fn is_stuck_after_state_limit() {
+        let now = OffsetDateTime::now_utc();
+        let stuck = stuck_config();
+        assert!(RetryController::is_stuck(
+            &stuck,
+            &added_ago(QueueStatus::Queued, 7 * 3600),
+            now
+        ));
+        assert!(!RetryController::is_stuck(
+            &stuck,
+            &added_ago(QueueStatus::Queued, 5 * 3600),
+            now
+        ));
+        assert!(RetryController::is_stuck(
+            &stuck,
+            &added_ago(QueueStatus::DownloadClientUnavailable, 7200),
+            now
+        ));
+    }
+
+    #[test]
+    fn is_stuck_false_without_limit_or_added() {
+        let now = OffsetDateTime::now_utc();
+        let stuck = stuck_config();
+        assert!(!RetryController::is_stuck(
+            &stuck,
+            &added_ago(QueueStatus::Delay, 30 * 24 * 3600),
+            now
+        ));
+        assert!(!RetryController::is_stuck(
+            &stuck,
+            &added_ago(QueueStatus::Downloading, 30 * 24 * 3600),
+            now
+        ));
+        let mut r = added_ago(QueueStatus::Queued, 0);
+        r.added = None;
+        assert!(!RetryController::is_stuck(&stuck, &r, now));
+    }
+
+    #[test]
+    fn is_stuck_downloading_metadata_uses_metadata_limit() {
+        let now = OffsetDateTime::now_utc();
+        let stuck = stuck_config();
+        let mut r = added_ago(QueueStatus::Queued, 3600);
+        r.error_message = Some("qBittorrent is downloading metadata".to_string());
+        assert!(RetryController::is_stuck(&stuck, &r, now));
+        r.added = Some(now - Duration::from_secs(600));
+        assert!(!RetryController::is_stuck(&stuck, &r, now));
+    }
+
+    // ── check_slow_download ───────────────────────────────────────────────────
+
+    fn slow_controller(checks: usize) -> RetryController {
+        let mut ctrl = make_controller();
+        ctrl.retry_config.slow = Some(SlowDownloadConfig {
+            min_speed: ByteSize(10_000),
+            checks,
+            large: Some(LargeReleaseConfig {
+                larger_than: ByteSize(10_000_000_000),
+                min_speed: ByteSize(1_000_000),
+            }),
+        });
+        ctrl
+    }
+
+    fn sample(sizeleft: i64, secs_ago: u64, slow_checks: usize) -> SpeedSample {
+        SpeedSample {
+            sizeleft,
+            at: OffsetDateTime::now_utc() - Duration::from_secs(secs_ago),
+            slow_checks,
+        }
+    }
+
+    #[test]
+    fn check_slow_download_disabled_without_config() {
+        let mut ctrl = make_controller();
+        let r = make_resource();
+        assert!(!ctrl.check_slow_download("abc123", &r, Some(0), OffsetDateTime::now_utc()));
+        assert!(ctrl.speeds.is_empty());
+    }
+
+    #[test]
+    fn check_slow_download_first_sample_only_records() {
+        let mut ctrl = slow_controller(1);
+        let r = make_resource();
+        assert!(!ctrl.check_slow_download("abc123", &r, None, OffsetDateTime::now_utc()));
+        assert_eq!(ctrl.speeds["abc123"].slow_checks, 0);
+        assert_eq!(ctrl.speeds["abc123"].sizeleft, 1_000_000);
+    }
+
+    #[test]
+    fn check_slow_download_uses_sizeleft_delta() {
+        let mut ctrl = slow_controller(3);
+        let mut r = make_resource();
+        r.sizeleft = 700_000;
+        // 300 KB in 300 s is 1 KB/s, below the 10 KB/s minimum
+        ctrl.speeds
+            .insert("abc123".to_string(), sample(1_000_000, 300, 0));
+        assert!(!ctrl.check_slow_download("abc123", &r, None, OffsetDateTime::now_utc()));
+        assert_eq!(ctrl.speeds["abc123"].slow_checks, 1);
+        assert_eq!(ctrl.speeds["abc123"].sizeleft, 700_000);
+    }
+
+    #[test]
+    fn check_slow_download_removes_after_consecutive_checks() {
+        let mut ctrl = slow_controller(3);
+        let mut r = make_resource();
+        r.sizeleft = 999_000;
+        ctrl.speeds
+            .insert("abc123".to_string(), sample(1_000_000, 300, 2));
+        assert!(ctrl.check_slow_download("abc123", &r, None, OffsetDateTime::now_utc()));
+        assert!(!ctrl.speeds.contains_key("abc123"));
+    }
+
+    #[test]
+    fn check_slow_download_fast_speed_resets_checks() {
+        let mut ctrl = slow_controller(3);
+        let mut r = make_resource();
+        r.size = 100_000_000;
+        r.sizeleft = 50_000_000;
+        ctrl.speeds
+            .insert("abc123".to_string(), sample(100_000_000, 300, 2));
+        assert!(!ctrl.check_slow_download("abc123", &r, None, OffsetDateTime::now_utc()));
+        assert_eq!(ctrl.speeds["abc123"].slow_checks, 0);
+    }
+
+    #[test]
+    fn check_slow_download_prefers_qbittorrent_speed() {
+        let mut ctrl = slow_controller(1);
+        let r = make_resource();
+        // No previous sample needed when qBittorrent reports the speed
+        assert!(ctrl.check_slow_download("abc123", &r, Some(5_000), OffsetDateTime::now_utc()));
+
+        let mut ctrl = slow_controller(1);
+        assert!(!ctrl.check_slow_download("abc123", &r, Some(50_000), OffsetDateTime::now_utc()));
+    }
+
+    #[test]
+    fn check_slow_download_large_release_threshold() {
+        let mut ctrl = slow_controller(1);
+        let mut r = make_resource();
+        r.size = 20_000_000_000;
+        r.sizeleft = 10_000_000_000;
+        // Fast enough for a small release, too slow for a large one
+        assert!(ctrl.check_slow_download("abc123", &r, Some(500_000), OffsetDateTime::now_utc()));
+    }
+
+    #[test]
+    fn check_slow_download_ignores_inactive_downloads() {
+        let mut ctrl = slow_controller(1);
+        ctrl.speeds
+            .insert("abc123".to_string(), sample(1_000_000, 300, 0));
+        let mut r = make_resource();
+        r.status = QueueStatus::Paused;
+        assert!(!ctrl.check_slow_download("abc123", &r, Some(0), OffsetDateTime::now_utc()));
+        assert!(!ctrl.speeds.contains_key("abc123"));
+    }
+
     // ── execute_removals ──────────────────────────────────────────────────────
 
+    fn removals(items: Vec<QueueResource>) -> Vec<Removal> {
+        items
+            .into_iter()
+            .map(|r| {
+                let verdict = QueueVerdict::new(&r, RetryRule::Stalled, "5/5 strikes".to_string());
+                (r, verdict)
+            })
+            .collect()
+    }
+
     #[tokio::test]
     async fn execute_removals_empty_is_noop() {
         let api = Arc::new(MockArrApi::new());
         let ctrl = make_controller();
         ctrl.execute_removals(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
-            vec![],
+            removals(vec![]),
             false,
+            true,
         )
         .await
         .unwrap();
@@ -619,14 +1930,144 @@ mod tests {
         let r = make_resource();
         ctrl.execute_removals(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
-            vec![r],
+            removals(vec![r]),
             false,
+            true,
         )
         .await
         .unwrap();
         assert!(api.delete_calls().is_empty());
     }
 
+    #[tokio::test]
+    async fn execute_removals_dry_run_records_reasons() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.dry_run = Some(true);
+        let mut r = make_resource();
+        r.sizeleft = 250_000;
+        let actions = ctrl
+            .execute_removals(
+                &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+                removals(vec![r]),
+                true,
+                true,
+            )
+            .await
+            .unwrap();
+        assert_eq!(actions[0].action, "dry_run");
+        assert_eq!(
+            actions[0].reason.as_deref(),
+            Some("stalled (5/5 strikes), Downloading/Downloading, 75% of 1000000 bytes downloaded")
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_removals_skips_api_after_shutdown() {
+        let api = Arc::new(MockArrApi::new());
+        let ctrl = make_controller();
+        ctrl.shutdown.request();
+        let result = ctrl
+            .execute_removals(
+                &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+                removals(vec![make_resource()]),
+                false,
+                true,
+            )
+            .await;
+        assert!(result.is_err());
+        assert!(api.delete_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_removals_searches_removed_items() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.search = true;
+        let mut episode = make_resource();
+        episode.id = 1;
+        episode.episode_id = Some(100);
+        let mut same_episode = make_resource();
+        same_episode.id = 2;
+        same_episode.episode_id = Some(100);
+        let mut movie = make_resource();
+        movie.id = 3;
+        movie.movie_id = Some(200);
+        let unknown = make_resource();
+        ctrl.execute_removals(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            removals(vec![episode, same_episode, movie, unknown]),
+            true,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.delete_calls().len(), 1);
+        assert_eq!(api.search_calls(), vec![vec![100, 200]]);
+    }
+
+    #[tokio::test]
+    async fn execute_removals_searches_every_episode_of_a_season_pack() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.search = true;
+        let mut queue: Vec<QueueResource> = [100, 101, 102]
+            .into_iter()
+            .map(|episode_id| sonarr_removable(episode_id, 10, episode_id))
+            .collect();
+        let mut other = sonarr_removable(200, 20, 200);
+        other.download_id = Some("def456".to_string());
+        queue.push(other);
+        QueueResource::group_episodes(&mut queue);
+        assert_eq!(queue[3].episode_ids, vec![200]);
+        ctrl.execute_removals(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            removals(vec![queue.swap_remove(0)]),
+            true,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.search_calls(), vec![vec![100, 101, 102]]);
+    }
+
+    #[tokio::test]
+    async fn execute_removals_does_not_search_by_default() {
+        let api = Arc::new(MockArrApi::new());
+        let ctrl = make_controller();
+        let mut r = make_resource();
+        r.episode_id = Some(100);
+        ctrl.execute_removals(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            removals(vec![r]),
+            true,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.delete_calls().len(), 1);
+        assert!(api.search_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_removals_dry_run_does_not_search() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.search = true;
+        ctrl.retry_config.dry_run = Some(true);
+        let mut r = make_resource();
+        r.episode_id = Some(100);
+        ctrl.execute_removals(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            removals(vec![r]),
+            false,
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(api.search_calls().is_empty());
+    }
+
     #[tokio::test]
     async fn execute_removals_calls_api_without_blocklist() {
         let api = Arc::new(MockArrApi::new());
@@ -635,8 +2076,9 @@ mod tests {
         r.id = 42;
         ctrl.execute_removals(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
-            vec![r],
+            removals(vec![r]),
             false,
+            true,
         )
         .await
         .unwrap();
@@ -654,7 +2096,8 @@ mod tests {
         r.id = 7;
         ctrl.execute_removals(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
-            vec![r],
+            removals(vec![r]),
+            true,
             true,
         )
         .await
@@ -676,6 +2119,7 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
         )
         .await
         .unwrap();
@@ -690,6 +2134,7 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
         )
         .await
         .unwrap();
@@ -715,6 +2160,7 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
         )
         .await
         .unwrap();
@@ -737,6 +2183,7 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
         )
         .await
         .unwrap();
@@ -762,6 +2209,7 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
         )
         .await
         .unwrap();
@@ -769,6 +2217,11 @@ mod tests {
         assert_eq!(calls.len(), 1);
         assert!(calls[0].0.contains(&30));
         assert_eq!(calls[0].1, Some(true)); // blocklisted
+        assert_eq!(ctrl.last_verdicts[0].rule, RetryRule::BannedMessage);
+        assert_eq!(
+            ctrl.last_verdicts[0].detail,
+            "Found potentially dangerous file"
+        );
     }
 
     #[tokio::test]
@@ -782,6 +2235,7 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
         )
         .await
         .unwrap();
@@ -792,11 +2246,84 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn process_queue_dry_run_does_not_call_api() {
+    async fn process_queue_stuck_item_without_download_id_removed() {
         let api = Arc::new(MockArrApi::new());
         let mut ctrl = make_controller();
-        ctrl.retry_config.dry_run = Some(true);
-        let mut r = make_resource();
+        ctrl.retry_config.stuck = Some(StuckConfig {
+            delay: Some(Duration::from_secs(3600)),
+            ..stuck_config()
+        });
+        let mut r = added_ago(QueueStatus::Delay, 7200);
+        r.id = 40;
+        r.download_id = None;
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![r],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        let calls = api.delete_calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].0.contains(&40));
+        assert_eq!(calls[0].1, Some(false)); // not blocklisted
+    }
+
+    #[tokio::test]
+    async fn process_queue_stuck_item_blocklisted_when_configured() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.stuck = Some(StuckConfig {
+            blocklist: true,
+            ..stuck_config()
+        });
+        let mut r = added_ago(QueueStatus::Queued, 7 * 3600);
+        r.id = 41;
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![r],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        let calls = api.delete_calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].0.contains(&41));
+        assert_eq!(calls[0].1, Some(true));
+    }
+
+    #[tokio::test]
+    async fn process_queue_verdict_names_first_matching_rule() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.dry_run = Some(true);
+        let mut r = make_resource();
+        r.status = QueueStatus::Warning;
+        r.added = Some(OffsetDateTime::now_utc() - Duration::from_secs(7200));
+        r.error_message = Some("The download is stalled with no connections".to_string());
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![r],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        let verdict = &ctrl.last_verdicts[0];
+        assert_eq!(verdict.rule, RetryRule::ZeroProgress);
+        assert_eq!(verdict.status, QueueStatus::Warning);
+        assert_eq!(
+            verdict.tracked_download_state,
+            TrackedDownloadState::Downloading
+        );
+        assert_eq!((verdict.size, verdict.sizeleft), (1_000_000, 1_000_000));
+    }
+
+    #[tokio::test]
+    async fn process_queue_dry_run_does_not_call_api() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.dry_run = Some(true);
+        let mut r = make_resource();
         r.status = QueueStatus::Completed;
         r.tracked_download_status = TrackedDownloadStatus::Warning;
         r.tracked_download_state = TrackedDownloadState::ImportPending;
@@ -807,12 +2334,434 @@ mod tests {
         ctrl.process_queue(
             &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
             vec![r],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert!(api.delete_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_queue_bad_contents_removed_and_blocklisted() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        ctrl.retry_config.contents = Some(contents_config());
+        let mut r = make_resource();
+        r.id = 31;
+        r.download_id = Some("ABC123".to_string());
+        let reports = ClientReports {
+            files: HashMap::from([("abc123".to_string(), files(&["Show.S01E01.exe"]))]),
+            ..ClientReports::default()
+        };
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![r],
+            &reports,
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.delete_calls(), vec![(vec![31], Some(true))]);
+        assert_eq!(ctrl.last_verdicts[0].rule, RetryRule::BadContents);
+        assert_eq!(ctrl.last_verdicts[0].detail, "denied file Show.S01E01.exe");
+    }
+
+    #[tokio::test]
+    async fn process_queue_bad_contents_ignored_when_disabled() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = make_controller();
+        let reports = ClientReports {
+            files: HashMap::from([("abc123".to_string(), files(&["Show.S01E01.exe"]))]),
+            ..ClientReports::default()
+        };
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![make_resource()],
+            &reports,
         )
         .await
         .unwrap();
         assert!(api.delete_calls().is_empty());
     }
 
+    // ── import_blocked ────────────────────────────────────────────────────────
+
+    fn import_blocked_resource() -> QueueResource {
+        let mut r = make_resource();
+        r.id = 50;
+        r.series_id = Some(7);
+        r.episode_id = Some(70);
+        r.status = QueueStatus::Completed;
+        r.tracked_download_status = TrackedDownloadStatus::Warning;
+        r.tracked_download_state = TrackedDownloadState::ImportBlocked;
+        r
+    }
+
+    fn candidate(series_id: i32, episode_ids: Vec<i32>) -> ImportCandidate {
+        ImportCandidate {
+            path: "/downloads/Some.Show.S01E01/episode.mkv".to_string(),
+            series_id: Some(series_id),
+            episode_ids,
+            movie_id: None,
+            rejections: vec![],
+        }
+    }
+
+    fn import_blocked_controller(timeout: Option<Duration>) -> RetryController {
+        let mut ctrl = make_controller();
+        ctrl.retry_config.import_blocked = Some(ImportBlockedConfig {
+            manual_import: true,
+            timeout,
+        });
+        ctrl
+    }
+
+    #[test]
+    fn is_import_blocked_requires_completed_warning() {
+        let r = import_blocked_resource();
+        assert!(RetryController::is_import_blocked(&r));
+        let mut pending = r.clone();
+        pending.tracked_download_state = TrackedDownloadState::ImportPending;
+        assert!(RetryController::is_import_blocked(&pending));
+        let mut ok = r.clone();
+        ok.tracked_download_status = TrackedDownloadStatus::Ok;
+        assert!(!RetryController::is_import_blocked(&ok));
+        let mut downloading = r;
+        downloading.status = QueueStatus::Downloading;
+        assert!(!RetryController::is_import_blocked(&downloading));
+    }
+
+    #[test]
+    fn safe_import_only_single_matching_file() {
+        let r = import_blocked_resource();
+        assert!(RetryController::safe_import(&r, &[candidate(7, vec![70])]).is_some());
+        assert!(RetryController::safe_import(&r, &[]).is_none());
+        assert!(
+            RetryController::safe_import(&r, &[candidate(7, vec![70]), candidate(7, vec![71])])
+                .is_none()
+        );
+        assert!(RetryController::safe_import(&r, &[candidate(8, vec![70])]).is_none());
+        assert!(RetryController::safe_import(&r, &[candidate(7, vec![70, 71])]).is_none());
+        let rejected = ImportCandidate {
+            rejections: vec!["Not an upgrade for existing episode file(s)".to_string()],
+            ..candidate(7, vec![70])
+        };
+        assert!(RetryController::safe_import(&r, &[rejected]).is_none());
+    }
+
+    #[test]
+    fn safe_import_matches_movie() {
+        let mut r = make_resource();
+        r.movie_id = Some(3);
+        let movie = ImportCandidate {
+            series_id: None,
+            episode_ids: vec![],
+            movie_id: Some(3),
+            ..candidate(0, vec![])
+        };
+        assert!(RetryController::safe_import(&r, std::slice::from_ref(&movie)).is_some());
+        r.movie_id = Some(4);
+        assert!(RetryController::safe_import(&r, &[movie]).is_none());
+    }
+
+    #[tokio::test]
+    async fn process_queue_imports_blocked_download_once() {
+        let api = Arc::new(MockArrApi {
+            candidates: vec![candidate(7, vec![70])],
+            ..MockArrApi::new()
+        });
+        let mut ctrl = import_blocked_controller(None);
+        for _ in 0..2 {
+            ctrl.process_queue(
+                &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+                vec![import_blocked_resource()],
+                &ClientReports::default(),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(
+            api.import_calls(),
+            vec![(
+                "abc123".to_string(),
+                vec!["/downloads/Some.Show.S01E01/episode.mkv".to_string()]
+            )]
+        );
+        assert!(api.delete_calls().is_empty());
+        assert_eq!(ctrl.last_actions[0].action, "manual_import");
+    }
+
+    #[tokio::test]
+    async fn process_queue_does_not_import_unsafe_download() {
+        let api = Arc::new(MockArrApi {
+            candidates: vec![candidate(7, vec![70]), candidate(7, vec![71])],
+            ..MockArrApi::new()
+        });
+        let mut ctrl = import_blocked_controller(None);
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert!(api.import_calls().is_empty());
+        assert!(ctrl.blocked["abc123"].attempted);
+    }
+
+    #[tokio::test]
+    async fn process_queue_removes_blocked_download_after_timeout() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = import_blocked_controller(Some(Duration::from_secs(3600)));
+        ctrl.blocked.insert(
+            "abc123".to_string(),
+            BlockedImport {
+                since: OffsetDateTime::now_utc() - Duration::from_secs(1800),
+                attempted: true,
+                command: None,
+            },
+        );
+        let api_dyn = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
+        ctrl.process_queue(
+            &api_dyn,
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert!(api.delete_calls().is_empty());
+
+        ctrl.blocked.get_mut("abc123").unwrap().since =
+            OffsetDateTime::now_utc() - Duration::from_secs(7200);
+        ctrl.process_queue(
+            &api_dyn,
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        let calls = api.delete_calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].0.contains(&50));
+        assert_eq!(calls[0].1, Some(true)); // blocklisted
+    }
+
+    #[tokio::test]
+    async fn process_queue_keeps_blocked_download_while_import_runs() {
+        let api = Arc::new(MockArrApi {
+            candidates: vec![candidate(7, vec![70])],
+            import_running: AtomicBool::new(true),
+            ..MockArrApi::new()
+        });
+        let api_dyn = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
+        let mut ctrl = import_blocked_controller(Some(Duration::from_secs(3600)));
+        ctrl.process_queue(
+            &api_dyn,
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ctrl.blocked["abc123"].command, Some(1));
+
+        let long_ago = OffsetDateTime::now_utc() - Duration::from_secs(7200);
+        ctrl.blocked.get_mut("abc123").unwrap().since = long_ago;
+        ctrl.process_queue(
+            &api_dyn,
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert!(api.delete_calls().is_empty());
+        assert!(ctrl.blocked["abc123"].since > long_ago);
+
+        api.import_running.store(false, Ordering::Relaxed);
+        ctrl.blocked.get_mut("abc123").unwrap().since = long_ago;
+        ctrl.process_queue(
+            &api_dyn,
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.delete_calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn process_queue_dry_run_does_not_import() {
+        let api = Arc::new(MockArrApi {
+            candidates: vec![candidate(7, vec![70])],
+            ..MockArrApi::new()
+        });
+        let mut ctrl = import_blocked_controller(None);
+        ctrl.retry_config.dry_run = Some(true);
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![import_blocked_resource()],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert!(api.import_calls().is_empty());
+        assert_eq!(ctrl.last_actions[0].action, "dry_run");
+    }
+
+    // ── max_retries ───────────────────────────────────────────────────────────
+
+    fn max_retries_controller(count: usize, per: RetryScope, unmonitor: bool) -> RetryController {
+        let mut ctrl = make_controller();
+        ctrl.retry_config.max_retries = Some(MaxRetriesConfig {
+            count,
+            window: Duration::from_secs(24 * 3600),
+            per,
+            unmonitor,
+        });
+        ctrl
+    }
+
+    fn sonarr_removable(id: i32, series_id: i32, episode_id: i32) -> QueueResource {
+        let mut r = make_removable_resource(id);
+        r.series_id = Some(series_id);
+        r.episode_id = Some(episode_id);
+        r
+    }
+
+    #[test]
+    fn retry_target_follows_scope() {
+        let r = sonarr_removable(1, 10, 100);
+        let ctrl = max_retries_controller(3, RetryScope::Episode, false);
+        assert_eq!(ctrl.retry_target(&r), Some(MediaItem::Episode(100)));
+        let ctrl = max_retries_controller(3, RetryScope::Series, false);
+        assert_eq!(ctrl.retry_target(&r), Some(MediaItem::Series(10)));
+
+        let mut movie = make_resource();
+        movie.movie_id = Some(200);
+        assert_eq!(ctrl.retry_target(&movie), Some(MediaItem::Movie(200)));
+        assert_eq!(make_controller().retry_target(&r), None);
+    }
+
+    #[test]
+    fn record_removal_counts_within_window() {
+        let mut ctrl = max_retries_controller(2, RetryScope::Episode, false);
+        let now = OffsetDateTime::now_utc();
+        let target = MediaItem::Episode(100);
+        ctrl.removals
+            .insert(target, vec![now - Duration::from_secs(2 * 24 * 3600)]);
+        // The removal outside the window no longer counts
+        assert!(!ctrl.record_removal(target, now));
+        assert!(ctrl.record_removal(target, now));
+        assert!(!ctrl.record_removal(MediaItem::Episode(101), now));
+    }
+
+    #[tokio::test]
+    async fn process_queue_gives_up_after_max_retries() {
+        let api = Arc::new(MockArrApi::new());
+        let (notifier, mut notifications) = Notifier::channel();
+        let mut ctrl = max_retries_controller(2, RetryScope::Episode, true);
+        ctrl.retry_config.search = true;
+        ctrl.notifier = Some(notifier);
+        let sonarr = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
+
+        ctrl.process_queue(
+            &sonarr,
+            vec![sonarr_removable(1, 10, 100)],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.search_calls(), vec![vec![100]]);
+        assert!(api.unmonitor_calls().is_empty());
+
+        ctrl.process_queue(
+            &sonarr,
+            vec![sonarr_removable(2, 10, 100)],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        let calls = api.delete_calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[1].0.contains(&2));
+        // No new search for the item that ran out of retries
+        assert_eq!(api.search_calls().len(), 1);
+        assert_eq!(api.unmonitor_calls(), vec![vec![MediaItem::Episode(100)]]);
+        let notification = notifications.try_recv().unwrap();
+        assert!(notification.message.contains("Test Torrent"));
+        assert!(notification.message.contains("unmonitored"));
+    }
+
+    #[tokio::test]
+    async fn process_queue_dry_run_does_not_give_up() {
+        let api = Arc::new(MockArrApi::new());
+        let (notifier, mut notifications) = Notifier::channel();
+        let mut ctrl = max_retries_controller(1, RetryScope::Episode, true);
+        ctrl.retry_config.dry_run = Some(true);
+        ctrl.notifier = Some(notifier);
+        ctrl.process_queue(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            vec![sonarr_removable(1, 10, 100)],
+            &ClientReports::default(),
+        )
+        .await
+        .unwrap();
+        assert!(api.unmonitor_calls().is_empty());
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn process_queue_dry_run_config_does_not_count_removals() {
+        let api = Arc::new(MockArrApi::new());
+        let mut ctrl = max_retries_controller(2, RetryScope::Episode, false);
+        ctrl.retry_config.dry_run = Some(true);
+        let sonarr = api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>;
+        for id in 1..=3 {
+            ctrl.process_queue(
+                &sonarr,
+                vec![sonarr_removable(id, 10, 100)],
+                &ClientReports::default(),
+            )
+            .await
+            .unwrap();
+        }
+        assert!(ctrl.removals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_queue_failed_removal_is_not_counted() {
+        let mut api = MockArrApi::new();
+        api.fail_delete = true;
+        let mut ctrl = max_retries_controller(2, RetryScope::Episode, false);
+        let result = ctrl
+            .process_queue(
+                &(Arc::new(api) as Arc<dyn SonarrAndRadarrAPIInterface>),
+                vec![sonarr_removable(1, 10, 100)],
+                &ClientReports::default(),
+            )
+            .await;
+        assert!(result.is_err());
+        assert!(ctrl.removals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn give_up_unmonitors_each_target_once() {
+        let api = Arc::new(MockArrApi::new());
+        let ctrl = max_retries_controller(1, RetryScope::Series, true);
+        let items = vec![
+            sonarr_removable(1, 10, 100),
+            sonarr_removable(2, 20, 200),
+            sonarr_removable(3, 10, 101),
+        ];
+        ctrl.give_up(
+            &(api.clone() as Arc<dyn SonarrAndRadarrAPIInterface>),
+            &items,
+        )
+        .await;
+        assert_eq!(
+            api.unmonitor_calls(),
+            vec![vec![MediaItem::Series(10), MediaItem::Series(20)]]
+        );
+    }
+
     // ── run ───────────────────────────────────────────────────────────────────
 
     #[tokio::test]
@@ -824,10 +2773,27 @@ mod tests {
                 schedule: "0 * * * *".parse().unwrap(),
                 timeout: None,
                 dry_run: None,
+                run_on_start: false,
+                slow: None,
+                stuck: None,
+                search: false,
+                max_retries: None,
+                import_blocked: None,
+                usenet: None,
+                contents: None,
             },
             sonarr: sonarr.clone(),
             radarr: radarr.clone(),
+            qbittorrent: None,
+            usenet: Vec::new(),
+            notifier: None,
+            shutdown: ShutdownSignal::default(),
             strikes: HashMap::new(),
+            speeds: HashMap::new(),
+            removals: HashMap::new(),
+            blocked: HashMap::new(),
+            last_verdicts: Vec::new(),
+            last_actions: Vec::new(),
         };
         ctrl.run().await.unwrap();
         // Each API receives a delete call for its own item only
@@ -848,10 +2814,27 @@ mod tests {
                 schedule: "0 * * * *".parse().unwrap(),
                 timeout: None,
                 dry_run: None,
+                run_on_start: false,
+                slow: None,
+                stuck: None,
+                search: false,
+                max_retries: None,
+                import_blocked: None,
+                usenet: None,
+                contents: None,
             },
             sonarr: sonarr.clone(),
             radarr: radarr.clone(),
+            qbittorrent: None,
+            usenet: Vec::new(),
+            notifier: None,
+            shutdown: ShutdownSignal::default(),
             strikes: HashMap::new(),
+            speeds: HashMap::new(),
+            removals: HashMap::new(),
+            blocked: HashMap::new(),
+            last_verdicts: Vec::new(),
+            last_actions: Vec::new(),
         };
         ctrl.run().await.unwrap();
         assert!(sonarr.delete_calls().is_empty());
@@ -865,10 +2848,27 @@ mod tests {
                 schedule: "0 * * * *".parse().unwrap(),
                 timeout: None,
                 dry_run: None,
+                run_on_start: false,
+                slow: None,
+                stuck: None,
+                search: false,
+                max_retries: None,
+                import_blocked: None,
+                usenet: None,
+                contents: None,
             },
             sonarr: Arc::new(MockArrApi::failing_queue()),
             radarr: Arc::new(MockArrApi::new()),
+            qbittorrent: None,
+            usenet: Vec::new(),
+            notifier: None,
+            shutdown: ShutdownSignal::default(),
             strikes: HashMap::new(),
+            speeds: HashMap::new(),
+            removals: HashMap::new(),
+            blocked: HashMap::new(),
+            last_verdicts: Vec::new(),
+            last_actions: Vec::new(),
         };
         assert!(ctrl.run().await.is_err());
     }
@@ -880,14 +2880,124 @@ mod tests {
                 schedule: "0 * * * *".parse().unwrap(),
                 timeout: None,
                 dry_run: None,
+                run_on_start: false,
+                slow: None,
+                stuck: None,
+                search: false,
+                max_retries: None,
+                import_blocked: None,
+                usenet: None,
+                contents: None,
             },
             sonarr: Arc::new(MockArrApi::new()),
             radarr: Arc::new(MockArrApi::failing_queue()),
+            qbittorrent: None,
+            usenet: Vec::new(),
+            notifier: None,
+            shutdown: ShutdownSignal::default(),
             strikes: HashMap::new(),
+            speeds: HashMap::new(),
+            removals: HashMap::new(),
+            blocked: HashMap::new(),
+            last_verdicts: Vec::new(),
+            last_actions: Vec::new(),
         };
         assert!(ctrl.run().await.is_err());
     }
 
+    #[tokio::test]
+    async fn run_removes_slow_download_reported_by_qbittorrent() {
+        let mut r = make_resource();
+        r.id = 7;
+        r.download_id = Some("ABC123".to_string());
+        let sonarr = Arc::new(MockArrApi::with_queue(vec![r]));
+        let mut ctrl = slow_controller(1);
+        ctrl.sonarr = sonarr.clone();
+        ctrl.qbittorrent = Some(Arc::new(MockQBitApi {
+            speeds: vec![("abc123".to_string(), 1_000)],
+        }));
+        ctrl.run().await.unwrap();
+        let calls = sonarr.delete_calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].0.contains(&7));
+        assert_eq!(calls[0].1, Some(true)); // blocklisted
+    }
+
+    #[tokio::test]
+    async fn run_forgets_speed_of_downloads_no_longer_queued() {
+        let mut ctrl = slow_controller(3);
+        ctrl.speeds
+            .insert("gone".to_string(), sample(1_000_000, 300, 2));
+        ctrl.run().await.unwrap();
+        assert!(ctrl.speeds.is_empty());
+    }
+
+    // ── usenet ────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn run_removes_download_failed_in_usenet_client() {
+        let mut r = make_resource();
+        r.id = 8;
+        r.download_id = Some("SABnzbd_nzo_1".to_string());
+        r.status = QueueStatus::Failed;
+        let sonarr = Arc::new(MockArrApi::with_queue(vec![r]));
+        let usenet = Arc::new(MockUsenetApi::new(vec![failed_job(
+            "sabnzbd_nzo_1",
+            UsenetFailure::RepairFailed,
+        )]));
+        let mut ctrl = usenet_controller(sonarr.clone(), usenet.clone(), true);
+        ctrl.run().await.unwrap();
+        assert_eq!(sonarr.delete_calls(), vec![(vec![8], Some(true))]);
+        assert_eq!(ctrl.last_verdicts[0].rule, RetryRule::UsenetFailure);
+        assert_eq!(
+            ctrl.last_verdicts[0].detail,
+            "repair_failed: Repair failed, not enough repair blocks"
+        );
+        // Still queued in this run, so the *arr deletes the job itself
+        assert!(usenet.deleted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_keeps_download_with_unconfigured_usenet_failure() {
+        let mut r = make_resource();
+        r.download_id = Some("nzo_2".to_string());
+        let sonarr = Arc::new(MockArrApi::with_queue(vec![r]));
+        let usenet = Arc::new(MockUsenetApi::new(vec![failed_job(
+            "nzo_2",
+            UsenetFailure::Other,
+        )]));
+        let mut ctrl = usenet_controller(sonarr.clone(), usenet, false);
+        ctrl.run().await.unwrap();
+        assert!(sonarr.delete_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_cleans_failed_jobs_no_longer_queued() {
+        let mut completed = failed_job("done", UsenetFailure::Other);
+        completed.failure = None;
+        let usenet = Arc::new(MockUsenetApi::new(vec![
+            failed_job("gone", UsenetFailure::Other),
+            completed,
+        ]));
+        let mut ctrl = usenet_controller(Arc::new(MockArrApi::new()), usenet.clone(), true);
+        ctrl.run().await.unwrap();
+        assert_eq!(usenet.deleted(), vec!["nzo_gone".to_string()]);
+        assert_eq!(ctrl.last_actions.len(), 1);
+        assert_eq!(ctrl.last_actions[0].action, "delete");
+    }
+
+    #[tokio::test]
+    async fn run_dry_run_does_not_clean_failed_jobs() {
+        let usenet = Arc::new(MockUsenetApi::new(vec![failed_job(
+            "gone",
+            UsenetFailure::Encrypted,
+        )]));
+        let mut ctrl = usenet_controller(Arc::new(MockArrApi::new()), usenet.clone(), true);
+        ctrl.execute_dry_run().await.unwrap();
+        assert!(usenet.deleted().is_empty());
+        assert_eq!(ctrl.last_actions[0].action, "dry_run");
+    }
+
     // ── Task ──────────────────────────────────────────────────────────────────
 
     #[test]
@@ -904,12 +3014,69 @@ mod tests {
                 schedule: "0 * * * *".parse().unwrap(),
                 timeout: None,
                 dry_run: None,
+                run_on_start: false,
+                slow: None,
+                stuck: None,
+                search: false,
+                max_retries: None,
+                import_blocked: None,
+                usenet: None,
+                contents: None,
             },
             sonarr: sonarr.clone(),
             radarr,
+            qbittorrent: None,
+            usenet: Vec::new(),
+            notifier: None,
+            shutdown: ShutdownSignal::default(),
             strikes: HashMap::new(),
+            speeds: HashMap::new(),
+            removals: HashMap::new(),
+            blocked: HashMap::new(),
+            last_verdicts: Vec::new(),
+            last_actions: Vec::new(),
         };
         ctrl.execute().await.unwrap();
         assert!(sonarr.delete_calls()[0].0.contains(&99));
+        let report = ctrl.report().unwrap();
+        assert_eq!(report.actions().len(), 1);
+        assert_eq!(report.actions()[0].action, "remove_and_blocklist");
+    }
+
+    #[tokio::test]
+    async fn task_execute_dry_run_changes_nothing() {
+        let mut stalled = make_resource();
+        stalled.id = 5;
+        stalled.download_id = Some("stalled".to_string());
+        stalled.status = QueueStatus::Warning;
+        stalled.error_message = Some("The download is stalled".to_string());
+        let sonarr = Arc::new(MockArrApi::with_queue(vec![
+            make_removable_resource(99),
+            stalled,
+        ]));
+        let mut ctrl = make_controller();
+        ctrl.sonarr = sonarr.clone();
+        ctrl.execute_dry_run().await.unwrap();
+        assert!(sonarr.delete_calls().is_empty());
+        assert!(ctrl.strikes.is_empty());
+        assert_eq!(ctrl.retry_config.dry_run, None);
+        assert_eq!(ctrl.report().unwrap().actions()[0].action, "dry_run");
+    }
+
+    #[test]
+    fn task_report_lists_strikes() {
+        let mut ctrl = make_controller();
+        let now = OffsetDateTime::now_utc();
+        ctrl.strikes
+            .insert("b".to_string(), StrikeData::new(2, 100, now));
+        ctrl.strikes
+            .insert("a".to_string(), StrikeData::new(1, 100, now));
+        let Some(TaskReport::Retry { strikes, .. }) = ctrl.report() else {
+            panic!("expected a retry report");
+        };
+        let ids: Vec<&str> = strikes.iter().map(|s| s.download_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(strikes[1].strikes, 2);
+        assert_eq!(strikes[1].max_strikes, MAX_NUM_STRIKES);
     }
 }